@@ -1,18 +1,25 @@
-use crate::{Cursor, List, Node};
-use std::mem;
+use crate::{AllocError, Allocator, Cursor, CursorOrigin, Global, List, Node, NodeBox};
+use core::mem;
+use core::ptr;
 
-impl<T> List<T> {
+impl<T, A: Allocator> List<T, A> {
     /// Return a cursor at the beginning of the list (before the first node).
-    pub fn cursor(&mut self) -> Cursor<'_, T> {
+    pub fn cursor(&mut self) -> Cursor<'_, T, A> {
+        let head: *mut _ = &mut self.head;
         Cursor {
             position: 0,
             list_len: &mut self.len,
-            next_link: &mut self.head,
+            list_tail: &mut self.tail,
+            list_pool: &mut self.pool,
+            list_alloc: &self.alloc,
+            prev_node: ptr::null_mut(),
+            next_link: unsafe { &mut *head },
+            origin: CursorOrigin::head(head),
         }
     }
 }
 
-impl<'a, T> Cursor<'a, T> {
+impl<'a, T, A: Allocator> Cursor<'a, T, A> {
     /// A read-only reference to the following node's value.
     /// Return `None` if the cursor is past the end of the list.
     pub fn value(&self) -> Option<&T> {
@@ -31,7 +38,11 @@ impl<'a, T> Cursor<'a, T> {
         let next_link: *mut _ = self.next_link;
         unsafe {
             if let Some(ref mut node) = *next_link {
-                self.next_link = &mut node.next;
+                // Both pointers must derive from the same pointer to the node,
+                // `prev_node` may end up as the tail of the list.
+                let node = node.as_ptr();
+                self.prev_node = node;
+                self.next_link = &mut (*node).next;
                 self.position += 1;
             }
         }
@@ -61,12 +72,48 @@ impl<'a, T> Cursor<'a, T> {
     }
 
     /// Returns a copy of the cursor, freezing `self` while the copy is alive.
-    pub fn checkpoint(&mut self) -> Cursor<'_, T> {
+    ///
+    /// The copy rewinds to the position of `self`.
+    pub fn checkpoint(&mut self) -> Cursor<'_, T, A> {
+        let next_link: *mut _ = self.next_link;
         Cursor {
             position: self.position,
             list_len: self.list_len,
-            next_link: self.next_link,
+            list_tail: self.list_tail,
+            list_pool: self.list_pool,
+            list_alloc: self.list_alloc,
+            prev_node: self.prev_node,
+            next_link: unsafe { &mut *next_link },
+            origin: CursorOrigin {
+                next_link,
+                prev_node: self.prev_node,
+                position: self.position,
+            },
+        }
+    }
+
+    /// Move the cursor back to where it started from in O(1): the beginning
+    /// of the list, the position of the cursor it was checkpointed from, or
+    /// the position of the mutable iterator it was converted from.
+    pub fn rewind(&mut self) {
+        self.next_link = unsafe { &mut *self.origin.next_link };
+        self.prev_node = self.origin.prev_node;
+        self.position = self.origin.position;
+    }
+
+    /// Move the cursor to `position` from the beginning of the list, in
+    /// O(position) when moving backward, and O(position - self.position)
+    /// otherwise.
+    ///
+    /// Returns the new position, which could be less than `position` if there
+    /// is not enough nodes, or more if `position` is before where the cursor
+    /// started from.
+    pub fn seek(&mut self, position: usize) -> usize {
+        if position < self.position {
+            self.rewind();
         }
+        self.nth(position.saturating_sub(self.position));
+        self.position
     }
 
     /// Move forward by `nth` nodes in O(min(nth, self.len)).
@@ -100,57 +147,126 @@ impl<'a, T> Cursor<'a, T> {
 
     /// Create a new node containing the value `v` and insert it at the current
     /// location in O(1).
-    pub fn insert(&mut self, v: T) -> &mut T {
-        let mut new_node = Node::new_boxed(v, self.next_link.take());
-        let value: *mut _ = &mut new_node.value;
-        let next: *mut _ = &mut new_node.next;
+    pub fn insert(&mut self, v: T) -> &mut T
+    where
+        A: Clone,
+    {
+        let node = NodeBox::from_pool(self.list_pool, v, self.list_alloc);
+        self.insert_node(node)
+    }
+
+    /// Create a new node containing the value `v` and insert it at the current
+    /// location in O(1).
+    /// Returns an error, leaving the list unchanged, if the allocation fails.
+    pub fn try_insert(&mut self, v: T) -> Result<&mut T, AllocError>
+    where
+        A: Clone,
+    {
+        let node = NodeBox::try_from_pool(self.list_pool, v, self.list_alloc)?;
+        Ok(self.insert_node(node))
+    }
+
+    /// Insert the detached node `node` at the current location in O(1),
+    /// without allocating.
+    pub fn insert_node(&mut self, node: NodeBox<T, A>) -> &mut T {
+        let mut new_node = node.0;
+        new_node.next = self.next_link.take();
+        let node = new_node.as_ptr();
+        if new_node.next.is_none() {
+            *self.list_tail = node;
+        }
         *self.next_link = Some(new_node);
         *self.list_len += 1;
         self.position += 1;
+        self.prev_node = node;
         unsafe {
-            self.next_link = &mut *next;
-            &mut *value
+            self.next_link = &mut (*node).next;
+            &mut (*node).value
         }
     }
 
+    /// Insert all the values from `iter` at the current location in
+    /// O(iter.len()), moving the cursor past them.
+    /// Returns the number of inserted values.
+    ///
+    /// The values are linked to the list only once `iter` is exhausted, if it
+    /// panics the list is left untouched.
+    pub fn insert_iter<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+        A: Clone,
+    {
+        let mut other = List::from_iter_pooled(iter, self.list_pool, self.list_alloc);
+        let count = other.len();
+        self.splice(&mut other);
+        count
+    }
+
     /// Remove the following node and return the contained value in O(1).
     /// Return `None` if the cursor is past the end of the list.
     pub fn remove(&mut self) -> Option<T> {
+        let node = self.remove_node()?;
+        Some(node.recycle(self.list_pool))
+    }
+
+    /// Unlink the following node in O(1), without freeing it.
+    /// Return `None` if the cursor is past the end of the list.
+    pub fn remove_node(&mut self) -> Option<NodeBox<T, A>> {
         self.next_link.take().map(|mut node| {
             *self.next_link = node.next.take();
+            if self.next_link.is_none() {
+                *self.list_tail = self.prev_node;
+            }
             *self.list_len -= 1;
-            node.value
+            NodeBox(node)
         })
     }
 
     /// Truncate the list after the cursor, returning the tail in O(1).
-    pub fn truncate(&mut self) -> List<T> {
+    pub fn truncate(&mut self) -> List<T, A>
+    where
+        A: Clone,
+    {
         let tail_link = self.next_link.take();
         let tail_len = self.len();
         *self.list_len -= tail_len;
+        let tail_tail = if tail_link.is_some() {
+            mem::replace(self.list_tail, self.prev_node)
+        } else {
+            ptr::null_mut()
+        };
         List {
             len: tail_len,
             head: tail_link,
+            tail: tail_tail,
+            pool: None,
+            alloc: self.list_alloc.clone(),
         }
     }
 
-    fn assign_tail(&mut self, tail: &mut List<T>) {
+    fn assign_tail(&mut self, tail: &mut List<T, A>) {
         if cfg!(test) {
             assert!(self.next_link.is_none());
         }
+        if tail.is_empty() {
+            return;
+        }
         *self.next_link = tail.head.take();
+        *self.list_tail = mem::replace(&mut tail.tail, ptr::null_mut());
         *self.list_len += mem::replace(&mut tail.len, 0);
     }
 
-    /// Insert the list `other` after the cursor.
-    ///
-    ///  * In O(other.len()) if self.len > 0
-    ///  * O(1) if self.len == 0
-    pub fn splice(&mut self, other: &mut List<T>) {
-        let tail = self.truncate();
-        self.assign_tail(other);
-        self.end();
-        self.assign_tail(&mut { tail });
+    /// Insert the list `other` after the cursor in O(1), moving the cursor
+    /// past the inserted nodes.
+    pub fn splice(&mut self, other: &mut List<T, A>) {
+        let other_len = other.len();
+        let other_tail = other.link_before(self.next_link, self.list_len, self.list_tail);
+        if other_tail.is_null() {
+            return;
+        }
+        self.position += other_len;
+        self.prev_node = other_tail;
+        self.next_link = unsafe { &mut (*other_tail).next };
     }
 
     /// Split the list after `nth` and return the tail in O(min(at, self.len)).
@@ -163,7 +279,10 @@ impl<'a, T> Cursor<'a, T> {
     ///     c.truncate()
     /// }
     /// ```
-    pub fn split(&mut self, after: usize) -> List<T> {
+    pub fn split(&mut self, after: usize) -> List<T, A>
+    where
+        A: Clone,
+    {
         let mut c = self.checkpoint();
         c.nth(after);
         c.truncate()
@@ -171,91 +290,258 @@ impl<'a, T> Cursor<'a, T> {
 
     /// Remove `count` nodes after the cursor in O(min(count, self.len)).
     /// Return the removed list.
-    pub fn remove_n(&mut self, count: usize) -> List<T> {
+    pub fn remove_n(&mut self, count: usize) -> List<T, A>
+    where
+        A: Clone,
+    {
         let tail = self.split(count);
         let removed = self.truncate();
         self.assign_tail(&mut { tail });
         removed
     }
-}
 
-// TODO cursor iter?
-pub struct CursorIntoIter<'a, T> {
-    cursor: Cursor<'a, T>,
-    first: bool,
-}
-
-impl<'a, T> IntoIterator for Cursor<'a, T> {
-    type Item = &'a mut Cursor<'a, T>;
-    type IntoIter = CursorIntoIter<'a, T>;
+    /// Replace the `count` nodes after the cursor with the values of `iter`,
+    /// like `Vec::splice()`, in O(min(count, self.len) + iter.len()).
+    ///
+    /// * returns the removed nodes, which are relinked as they were, so the
+    ///   new values always get new nodes.
+    /// * the cursor moves past the inserted values.
+    pub fn replace_range<I>(&mut self, count: usize, iter: I) -> List<T, A>
+    where
+        I: IntoIterator<Item = T>,
+        A: Clone,
+    {
+        let removed = self.remove_n(count);
+        self.insert_iter(iter);
+        removed
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        CursorIntoIter {
-            cursor: self,
-            first: true,
+    /// Move forward past every node for which `pred` returns `true`, stopping
+    /// before the first one for which it returns `false`.
+    /// Returns the number of nodes skipped.
+    pub fn advance_while<F>(&mut self, mut pred: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut skipped = 0;
+        while self.value().is_some_and(&mut pred) {
+            self.advance();
+            skipped += 1;
         }
+        skipped
     }
-}
 
-impl<'a, T> Iterator for CursorIntoIter<'a, T> {
-    type Item = &'a mut Cursor<'a, T>;
+    /// Move forward before the first node for which `pred` returns `true`.
+    /// Returns `false` if there is no such node, leaving the cursor at the end
+    /// of the list.
+    pub fn seek_by<F>(&mut self, mut pred: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.advance_while(|v| !pred(v));
+        self.value().is_some()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if !self.first && !self.cursor.advance() {
-            None
+    /// Remove the nodes after the cursor for which `pred` returns `true`, up
+    /// to the first one for which it returns `false`.
+    /// Return the removed list.
+    pub fn take_while<F>(&mut self, pred: F) -> List<T, A>
+    where
+        F: FnMut(&T) -> bool,
+        A: Clone,
+    {
+        let tail = {
+            let mut c = self.checkpoint();
+            c.advance_while(pred);
+            c.truncate()
+        };
+        let removed = self.truncate();
+        self.assign_tail(&mut { tail });
+        removed
+    }
+
+    /// Remove the nodes after the cursor up to the first one for which `pred`
+    /// returns `true`.
+    /// Returns the number of nodes removed.
+    pub fn remove_until<F>(&mut self, mut pred: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+        A: Clone,
+    {
+        self.take_while(|v| !pred(v)).len()
+    }
+
+    /// Reverse the order of the `count` nodes after the cursor in
+    /// O(min(count, self.len)), without moving the cursor.
+    /// Returns the number of nodes reversed, which could be less than `count`
+    /// if there is not enough remaining nodes.
+    pub fn reverse_next(&mut self, count: usize) -> usize {
+        let mut rest = self.next_link.take();
+        let mut reversed = None;
+        let mut run_last: *mut Node<T, A> = ptr::null_mut();
+        let mut reversed_count = 0;
+        while reversed_count < count {
+            if let Some(mut node) = rest {
+                rest = node.next.take();
+                if run_last.is_null() {
+                    run_last = node.as_ptr();
+                }
+                node.next = reversed;
+                reversed = Some(node);
+                reversed_count += 1;
+            } else {
+                break;
+            }
+        }
+        if !run_last.is_null() {
+            if rest.is_none() {
+                *self.list_tail = run_last;
+            }
+            unsafe { (*run_last).next = rest };
+            *self.next_link = reversed;
         } else {
-            self.first = false;
-            unsafe { Some(mem::transmute(&mut self.cursor)) }
+            *self.next_link = rest;
         }
+        reversed_count
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.cursor.len();
-        (len, Some(len))
+    /// Among the `count` nodes after the cursor, remove the ones for which
+    /// `f` returns `false` in O(min(count, self.len)).
+    ///
+    /// The cursor moves past the retained nodes. Returns the number of nodes
+    /// removed.
+    ///
+    /// If `f` panics, the node being looked at and everything after it is
+    /// left untouched.
+    pub fn retain_next<F>(&mut self, count: usize, mut f: F) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut removed = 0;
+        for _ in 0..count {
+            let keep = match self.value_mut() {
+                Some(v) => f(v),
+                None => break,
+            };
+            if keep {
+                self.advance();
+            } else {
+                self.remove();
+                removed += 1;
+            }
+        }
+        removed
     }
 }
 
-pub struct CursorIterMut<'c, 'l: 'c, T> {
-    cursor: &'c mut Cursor<'l, T>,
+/// Lending iterator over the positions of a cursor, see `Cursor::positions()`.
+///
+/// The cursor yielded by `next()` borrows the iterator, so it must be dropped
+/// before moving to the next position. This does not compile:
+///
+/// ```compile_fail
+/// use fwdlist::List;
+///
+/// let mut l: List<_> = (0..3).collect();
+/// let mut c = l.cursor();
+/// let mut positions = c.positions();
+/// let a = positions.next().unwrap();
+/// let b = positions.next().unwrap();
+/// a.remove();
+/// b.remove();
+/// ```
+///
+/// And as a consequence, it cannot be an `Iterator`:
+///
+/// ```compile_fail
+/// use fwdlist::List;
+///
+/// let mut l: List<_> = (0..3).collect();
+/// let mut c = l.cursor();
+/// let cursors: Vec<_> = c.positions().collect();
+/// ```
+pub struct CursorPositions<'c, 'l, T, A: Allocator = Global> {
+    cursor: &'c mut Cursor<'l, T, A>,
     first: bool,
 }
 
-impl<'c, 'l, T> Cursor<'l, T> {
-    fn iter_mut(&'c mut self) -> CursorIterMut<'c, 'l, T> {
-        CursorIterMut {
+impl<'l, T, A: Allocator> Cursor<'l, T, A> {
+    /// Returns a lending iterator yielding the cursor in front of every
+    /// remaining node, starting with the current position.
+    ///
+    /// ```
+    /// use fwdlist::List;
+    ///
+    /// let mut l: List<_> = (0..5).collect();
+    /// let mut c = l.cursor();
+    /// let mut positions = c.positions();
+    /// while let Some(c) = positions.next() {
+    ///     if c.value() == Some(&2) {
+    ///         c.insert(42);
+    ///     }
+    /// }
+    /// assert_eq!(l, [0, 1, 42, 2, 3, 4].iter().cloned().collect());
+    /// ```
+    pub fn positions(&mut self) -> CursorPositions<'_, 'l, T, A> {
+        CursorPositions {
             cursor: self,
             first: true,
         }
     }
-}
 
-impl<'c, 'l, T> IntoIterator for &'c mut Cursor<'l, T> {
-    type Item = &'c mut Cursor<'l, T>;
-    type IntoIter = CursorIterMut<'c, 'l, T>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
+    /// Call `f` with the cursor in front of every remaining node, starting
+    /// with the current position.
+    ///
+    /// This is the same as:
+    ///
+    /// ```ignore
+    /// let mut positions = c.positions();
+    /// while let Some(c) = positions.next() {
+    ///     f(c);
+    /// }
+    /// ```
+    pub fn for_each_position<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut Cursor<'l, T, A>),
+    {
+        let mut positions = self.positions();
+        while let Some(c) = positions.next() {
+            f(c);
+        }
     }
 }
 
-impl<'c, 'l, T> Iterator for CursorIterMut<'c, 'l, T> {
-    type Item = &'c mut Cursor<'l, T>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if !self.first && !self.cursor.advance() {
+impl<'c, 'l, T, A: Allocator> CursorPositions<'c, 'l, T, A> {
+    /// Advance the cursor, except on the first call, and return it if it is in
+    /// front of a node.
+    // Cannot implement Iterator: the item borrows the iterator.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&mut Cursor<'l, T, A>> {
+        if self.first {
+            self.first = false;
+        } else {
+            self.cursor.advance();
+        }
+        if self.cursor.value().is_some() {
+            Some(self.cursor)
+        } else {
             None
+        }
+    }
+
+    /// The number of positions left, including the current one if `next()`
+    /// was not called yet.
+    pub fn len(&self) -> usize {
+        if self.first {
+            self.cursor.len()
         } else {
-            self.first = false;
-            unsafe {
-                let cursor: *mut _ = self.cursor;
-                Some(&mut *cursor)
-            }
+            self.cursor.len().saturating_sub(1)
         }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.cursor.len();
-        (len, Some(len))
+    /// Returns `true` if there is no position left.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
@@ -268,10 +554,10 @@ fn mklist<I: Iterator>(i: I) -> List<I::Item> {
 fn minimal() {
     let mut l = mklist(0..10);
     let mut i = 0;
-    for c in l.cursor() {
+    l.cursor().for_each_position(|c| {
         assert_eq!(c.value(), Some(&i));
         i += 1;
-    }
+    });
     assert_eq!(i, 10);
 }
 
@@ -279,11 +565,13 @@ fn minimal() {
 fn next() {
     let mut l = mklist(0..10);
     let mut i = 0;
-    for c in l.cursor() {
+    let mut c = l.cursor();
+    let mut positions = c.positions();
+    while let Some(c) = positions.next() {
         assert_eq!(c.len(), 10 - i);
         assert_eq!(c.value(), Some(&i));
         assert_eq!(c.value_mut(), Some(&mut i));
-        assert_eq!(c.advance(), true);
+        assert!(c.advance());
         i += 1;
         assert_eq!(c.len(), 10 - i);
         assert_eq!(c.value(), Some(&i));
@@ -297,19 +585,18 @@ fn next() {
 fn checkpoint() {
     let mut l = mklist(0..10);
     let mut i = 0;
-    for c in l.cursor() {
+    l.cursor().for_each_position(|c| {
         assert_eq!(c.value(), Some(&i));
         {
-            let c2 = c.checkpoint();
             let mut j = i;
-            for c2 in c2 {
+            c.checkpoint().for_each_position(|c2| {
                 assert_eq!(c2.value(), Some(&j));
                 j += 1;
-            }
+            });
             assert_eq!(j, 10);
         }
         i += 1;
-    }
+    });
     assert_eq!(i, 10);
 }
 
@@ -373,7 +660,7 @@ fn insert() {
         assert_eq!(c.position(), 0);
         assert_eq!(c.value(), Some(&42));
 
-        assert_eq!(c.advance(), true);
+        assert!(c.advance());
 
         assert_eq!(c.len(), 4);
         assert_eq!(c.position(), 1);
@@ -394,7 +681,7 @@ fn insert() {
         assert_eq!(c.value(), Some(&42));
 
         for _ in 0..5 {
-            assert_eq!(c.advance(), true);
+            assert!(c.advance());
         }
 
         assert_eq!(c.len(), 1);
@@ -416,15 +703,15 @@ fn insert() {
         assert_eq!(c.value(), Some(&42));
 
         for _ in 0..6 {
-            assert_eq!(c.advance(), true);
+            assert!(c.advance());
         }
-        assert_eq!(c.advance(), false);
+        assert!(!c.advance());
 
         assert_eq!(c.len(), 0);
         assert_eq!(c.position(), 7);
         assert_eq!(c.value(), None);
 
-        assert_eq!(c.advance(), false);
+        assert!(!c.advance());
 
         assert_eq!(c.len(), 0);
         assert_eq!(c.position(), 7);
@@ -472,7 +759,8 @@ fn remove() {
         assert_eq!(c.position(), 0);
         let mut i = 1;
         let mut pos = 0;
-        for c in &mut c {
+        let mut positions = c.positions();
+        while let Some(c) = positions.next() {
             assert_eq!(c.len(), 10 - i);
             assert_eq!(c.position(), pos);
             assert_eq!(c.value(), Some(&i));
@@ -612,6 +900,321 @@ fn remove_n() {
     assert_eq!(l, mklist([0, 1, 2, 7, 8, 9].iter().cloned()));
 }
 
+#[test]
+fn tail() {
+    let mut l = mklist(0..5);
+    {
+        let mut c = l.cursor();
+        c.end();
+        c.insert(5);
+        assert_eq!(*c.list_tail, c.prev_node);
+    }
+    l.check_links();
+    assert_eq!(l.back(), Some(&5));
+    {
+        let mut c = l.cursor();
+        c.last();
+        assert_eq!(c.remove(), Some(5));
+        assert_eq!(c.remove(), None);
+    }
+    l.check_links();
+    assert_eq!(l.back(), Some(&4));
+    let mut t = l.cursor().split(3);
+    l.check_links();
+    t.check_links();
+    assert_eq!(l.back(), Some(&2));
+    assert_eq!(t.back(), Some(&4));
+    {
+        let mut c = l.cursor();
+        c.nth(1);
+        c.splice(&mut t);
+        assert_eq!(c.position(), 3);
+    }
+    l.check_links();
+    t.check_links();
+    assert_eq!(l, mklist([0, 3, 4, 1, 2].iter().cloned()));
+    {
+        let mut c = l.cursor();
+        c.end();
+        c.splice(&mut mklist(5..7));
+        c.splice(&mut List::new());
+        c.insert(7);
+    }
+    l.check_links();
+    assert_eq!(l.back(), Some(&7));
+    let r = l.cursor().remove_n(8);
+    l.check_links();
+    r.check_links();
+    assert!(l.is_empty());
+    assert_eq!(r.back(), Some(&7));
+    l.push_back(8);
+    l.check_links();
+    {
+        let mut c = l.cursor();
+        assert_eq!(c.remove(), Some(8));
+        c.insert(9);
+    }
+    l.check_links();
+    assert_eq!(l.back(), Some(&9));
+    let r = {
+        let mut c = l.cursor();
+        c.nth(1);
+        c.truncate()
+    };
+    l.check_links();
+    r.check_links();
+    assert!(r.is_empty());
+}
+
+#[test]
+fn reverse_next() {
+    let mut l = mklist(0..10);
+    {
+        let mut c = l.cursor();
+        c.nth(2);
+        assert_eq!(c.reverse_next(4), 4);
+        assert_eq!(c.position(), 2);
+        assert_eq!(c.len(), 8);
+        assert_eq!(c.value(), Some(&5));
+    }
+    l.check_links();
+    assert_eq!(l, mklist([0, 1, 5, 4, 3, 2, 6, 7, 8, 9].iter().cloned()));
+    {
+        let mut c = l.cursor();
+        c.nth(7);
+        assert_eq!(c.reverse_next(10), 3);
+        assert_eq!(c.position(), 7);
+        assert_eq!(c.len(), 3);
+        c.end();
+        assert_eq!(c.reverse_next(1), 0);
+    }
+    l.check_links();
+    assert_eq!(l.back(), Some(&7));
+    assert_eq!(l, mklist([0, 1, 5, 4, 3, 2, 6, 9, 8, 7].iter().cloned()));
+    assert_eq!(l.cursor().reverse_next(0), 0);
+    assert_eq!(l.cursor().reverse_next(1), 1);
+    l.check_links();
+    assert_eq!(l.len(), 10);
+}
+
+#[test]
+fn retain_next() {
+    let mut l = mklist(0..10);
+    {
+        let mut c = l.cursor();
+        c.nth(2);
+        assert_eq!(c.retain_next(5, |v| *v % 2 == 0), 2);
+        assert_eq!(c.position(), 5);
+        assert_eq!(c.len(), 3);
+        assert_eq!(c.value(), Some(&7));
+        assert_eq!(c.retain_next(10, |v| *v > 8), 2);
+        assert_eq!(c.len(), 0);
+    }
+    l.check_links();
+    assert_eq!(l, mklist([0, 1, 2, 4, 6, 9].iter().cloned()));
+}
+
+#[test]
+fn positions() {
+    let mut l = mklist(0..6);
+    {
+        let mut c = l.cursor();
+        c.advance();
+        let mut positions = c.positions();
+        assert_eq!(positions.len(), 5);
+        let mut seen = Vec::new();
+        while let Some(c) = positions.next() {
+            seen.push(*c.value().unwrap());
+            if seen.len() == 2 {
+                c.remove();
+                c.insert(42);
+            }
+        }
+        assert!(positions.is_empty());
+        // The cursor was left in front of 3 after inserting 42.
+        assert_eq!(seen, [1, 2, 4, 5]);
+        assert_eq!(c.position(), 6);
+    }
+    assert_eq!(l, mklist([0, 1, 42, 3, 4, 5].iter().cloned()));
+
+    let mut l = List::<i32>::new();
+    let mut c = l.cursor();
+    assert!(c.positions().next().is_none());
+    c.for_each_position(|_| unreachable!());
+}
+
+#[test]
+fn rewind() {
+    let mut l = mklist(0..10);
+    let mut c = l.cursor();
+    c.nth(4);
+    c.rewind();
+    assert_eq!(c.position(), 0);
+    assert_eq!(c.len(), 10);
+    assert_eq!(c.value(), Some(&0));
+
+    assert_eq!(c.seek(7), 7);
+    assert_eq!(c.value(), Some(&7));
+    assert_eq!(c.seek(3), 3);
+    assert_eq!(c.value(), Some(&3));
+    assert_eq!(c.seek(20), 10);
+    assert_eq!(c.value(), None);
+    c.rewind();
+    c.last();
+    assert_eq!(c.remove(), Some(9));
+    c.rewind();
+    assert_eq!(c.remove(), Some(0));
+    assert_eq!(c.seek(3), 3);
+    {
+        let mut c2 = c.checkpoint();
+        c2.nth(2);
+        assert_eq!(c2.value(), Some(&6));
+        c2.rewind();
+        assert_eq!(c2.position(), 3);
+        assert_eq!(c2.value(), Some(&4));
+        assert_eq!(c2.seek(0), 3);
+        assert_eq!(c2.seek(5), 5);
+        c2.end();
+        c2.rewind();
+        c2.truncate();
+    }
+    c.rewind();
+    c.insert(-1);
+    assert_eq!(c.len(), 3);
+    l.check_links();
+    assert_eq!(l, mklist([-1, 1, 2, 3].iter().cloned()));
+}
+
+#[test]
+fn predicates() {
+    let mut l = mklist([1, 3, 5, 6, 7, 8, 10, 11, 12].iter().cloned());
+    {
+        let mut c = l.cursor();
+        assert_eq!(c.advance_while(|v| v % 2 == 1), 3);
+        assert_eq!(c.position(), 3);
+        assert_eq!(c.len(), 6);
+        assert_eq!(c.advance_while(|v| v % 2 == 1), 0);
+
+        assert!(c.seek_by(|v| *v > 7));
+        assert_eq!(c.value(), Some(&8));
+        assert_eq!(c.position(), 5);
+
+        assert_eq!(
+            c.take_while(|v| v % 2 == 0),
+            mklist([8, 10].iter().cloned())
+        );
+        assert_eq!(c.position(), 5);
+        assert_eq!(c.len(), 2);
+        assert_eq!(c.value(), Some(&11));
+        assert_eq!(c.take_while(|v| *v > 100), List::new());
+
+        c.rewind();
+        assert_eq!(c.remove_until(|v| *v == 6), 3);
+        assert_eq!(c.len(), 4);
+        assert_eq!(c.value(), Some(&6));
+
+        assert!(!c.seek_by(|v| *v > 100));
+        assert_eq!(c.position(), 4);
+        assert_eq!(c.len(), 0);
+        assert_eq!(c.nth(1), 0);
+    }
+    l.check_links();
+    assert_eq!(l, mklist([6, 7, 11, 12].iter().cloned()));
+    {
+        let mut c = l.cursor();
+        c.nth(2);
+        assert_eq!(c.remove_until(|_| false), 2);
+    }
+    l.check_links();
+    assert_eq!(l, mklist([6, 7].iter().cloned()));
+}
+
+#[test]
+fn replace_range() {
+    let mut l = mklist(0..10);
+    {
+        let mut c = l.cursor();
+        c.nth(2);
+        let removed = c.replace_range(3, 20..25);
+        removed.check_links();
+        assert_eq!(removed, mklist(2..5));
+        assert_eq!(c.position(), 7);
+        assert_eq!(c.len(), 5);
+        assert_eq!(c.value(), Some(&5));
+
+        assert_eq!(c.replace_range(2, None), mklist(5..7));
+        assert_eq!(c.position(), 7);
+        assert_eq!(c.value(), Some(&7));
+
+        c.end();
+        assert_eq!(c.replace_range(5, 30..32), List::new());
+        assert_eq!(c.position(), 12);
+    }
+    l.check_links();
+    assert_eq!(
+        l,
+        mklist([0, 1, 20, 21, 22, 23, 24, 7, 8, 9, 30, 31].iter().cloned())
+    );
+}
+
+#[test]
+fn insert_iter() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut l = mklist(0..4);
+    {
+        let mut c = l.cursor();
+        c.nth(2);
+        assert_eq!(c.insert_iter(10..13), 3);
+        assert_eq!(c.position(), 5);
+        assert_eq!(c.value(), Some(&2));
+        assert_eq!(c.insert_iter(None), 0);
+        c.end();
+        assert_eq!(c.insert_iter(vec![20, 21]), 2);
+        assert_eq!(c.len(), 0);
+    }
+    l.check_links();
+    assert_eq!(l, mklist([0, 1, 10, 11, 12, 2, 3, 20, 21].iter().cloned()));
+
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        let mut c = l.cursor();
+        c.advance();
+        c.insert_iter((0..5).map(|v| if v < 3 { v } else { panic!("boom") }));
+    }));
+    assert!(r.is_err());
+    l.check_links();
+    assert_eq!(l.len(), 9);
+
+    let mut l = List::with_node_pool(crate::NodePool::new(4));
+    l.extend(0..3);
+    l.clear();
+    assert_eq!(l.cursor().insert_iter(3..7), 4);
+    l.check_links();
+    let pool = l.node_pool().unwrap();
+    assert_eq!((pool.len(), pool.hits(), pool.misses()), (0, 3, 4));
+}
+
+#[test]
+fn nodes() {
+    let mut a = mklist(0..5);
+    let mut b = List::new();
+    {
+        let mut ca = a.cursor();
+        let mut cb = b.cursor();
+        ca.nth(1);
+        while let Some(node) = ca.remove_node() {
+            *cb.insert_node(node) *= 10;
+        }
+        assert_eq!(ca.remove_node().map(NodeBox::into_inner), None);
+        cb.rewind();
+        cb.insert_node(NodeBox::new(5));
+    }
+    a.check_links();
+    b.check_links();
+    assert_eq!(a, mklist(0..1));
+    assert_eq!(b, mklist([5, 10, 20, 30, 40].iter().cloned()));
+}
+
 //fn collect_cursor<'a, T: Clone>(mut c: Cursor<'a, T>) -> Vec<T> {
 //let mut r = Vec::new();
 //loop {
@@ -629,7 +1232,7 @@ fn remove_n() {
 fn merge_sort() {
     use std::fmt::Debug;
 
-    fn merge<'c, T>(mut a: List<T>, mut b: List<T>) -> List<T>
+    fn merge<T>(mut a: List<T>, mut b: List<T>) -> List<T>
     where
         T: Ord + Debug,
     {
@@ -0,0 +1,164 @@
+use crate::{Allocator, Global, Link, List, ListIter};
+
+/// A read-only cursor to navigate the list.
+///
+/// Like `Cursor`, it moves between nodes, but it only borrows the list, so it
+/// can be copied freely to remember a position and come back to it later.
+pub struct CursorRef<'a, T, A: Allocator = Global> {
+    next_link: &'a Link<T, A>,
+    list_len: usize,
+    position: usize,
+}
+
+// Can't use derive(Clone, Copy) here because it will require an extra Clone
+// bound for T which we don't need.
+impl<'a, T, A: Allocator> Clone for CursorRef<'a, T, A> {
+    fn clone(&self) -> CursorRef<'a, T, A> {
+        *self
+    }
+}
+
+impl<'a, T, A: Allocator> Copy for CursorRef<'a, T, A> {}
+
+impl<T, A: Allocator> List<T, A> {
+    /// Return a read-only cursor at the beginning of the list (before the
+    /// first node).
+    pub fn cursor_ref(&self) -> CursorRef<'_, T, A> {
+        CursorRef {
+            next_link: &self.head,
+            list_len: self.len,
+            position: 0,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> CursorRef<'a, T, A> {
+    /// A reference to the following node's value.
+    /// Return `None` if the cursor is past the end of the list.
+    pub fn value(&self) -> Option<&'a T> {
+        self.next_link.as_ref().map(|node| &node.value)
+    }
+
+    /// A reference to the value `nth` nodes after the following one in
+    /// O(min(nth, self.len)), without moving the cursor.
+    /// `peek_nth(0)` is the same as `value()`.
+    pub fn peek_nth(&self, nth: usize) -> Option<&'a T> {
+        let mut c = *self;
+        c.nth(nth);
+        c.value()
+    }
+
+    /// Move the cursor past the following node. Returns `true` on success,
+    /// `false` if the cursor is already at the end of the list.
+    pub fn advance(&mut self) -> bool {
+        if let Some(ref node) = *self.next_link {
+            self.next_link = &node.next;
+            self.position += 1;
+        }
+        self.next_link.is_some()
+    }
+
+    /// Move forward by `nth` nodes in O(min(nth, self.len)).
+    /// Returns the number of nodes skipped, which could be less than `nth` if
+    /// there is not enough remaining nodes.
+    pub fn nth(&mut self, nth: usize) -> usize {
+        let mut nthped = 0;
+        while nthped != nth && self.value().is_some() {
+            self.advance();
+            nthped += 1;
+        }
+        nthped
+    }
+
+    /// The lengths of the tail.
+    /// This is O(1).
+    pub fn len(&self) -> usize {
+        self.list_len - self.position
+    }
+
+    /// This is O(1).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The position from the beginning of the list.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns an iterator over the tail, yielding read-only references.
+    pub fn iter(&self) -> ListIter<'a, T, A> {
+        ListIter {
+            next_link: self.next_link,
+            len: self.len(),
+            list_len: self.list_len,
+        }
+    }
+}
+
+/// Convert the iterator into a read-only cursor in front of the next element.
+impl<'a, T, A: Allocator> From<ListIter<'a, T, A>> for CursorRef<'a, T, A> {
+    fn from(iter: ListIter<'a, T, A>) -> CursorRef<'a, T, A> {
+        CursorRef {
+            next_link: iter.next_link,
+            list_len: iter.list_len,
+            position: iter.list_len - iter.len,
+        }
+    }
+}
+
+/// Convert the read-only cursor into an iterator over the tail.
+impl<'a, T, A: Allocator> From<CursorRef<'a, T, A>> for ListIter<'a, T, A> {
+    fn from(c: CursorRef<'a, T, A>) -> ListIter<'a, T, A> {
+        c.iter()
+    }
+}
+
+#[test]
+fn cursor_ref() {
+    let l = (0..10).collect::<List<_>>();
+    let mut c = l.cursor_ref();
+    assert_eq!(c.len(), 10);
+    assert_eq!(c.value(), Some(&0));
+    assert_eq!(c.peek_nth(3), Some(&3));
+    assert_eq!(c.peek_nth(10), None);
+    assert_eq!(c.position(), 0);
+
+    assert!(c.advance());
+    let mark = c;
+    assert_eq!(c.nth(5), 5);
+    assert_eq!(c.position(), 6);
+    assert_eq!(c.len(), 4);
+    assert_eq!(c.value(), Some(&6));
+    assert_eq!(mark.position(), 1);
+    assert_eq!(mark.value(), Some(&1));
+
+    assert_eq!(c.iter().cloned().collect::<Vec<_>>(), [6, 7, 8, 9]);
+    assert_eq!(c.nth(10), 4);
+    assert!(!c.advance());
+    assert!(c.is_empty());
+    assert_eq!(c.value(), None);
+    assert_eq!(c.iter().len(), 0);
+
+    let c = mark;
+    assert_eq!(c.value(), Some(&1));
+}
+
+#[test]
+fn cursor_ref_iter() {
+    struct NotClonable(usize);
+    let l = (0..5).map(NotClonable).collect::<List<_>>();
+    let mut iter = l.iter();
+    iter.next();
+    iter.next();
+    let c = CursorRef::from(iter);
+    assert_eq!(c.position(), 2);
+    assert_eq!(c.len(), 3);
+    assert_eq!(c.value().map(|v| v.0), Some(2));
+
+    let c2 = c;
+    let iter = ListIter::from(c2);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.map(|v| v.0).sum::<usize>(), 9);
+    assert_eq!(c.value().map(|v| v.0), Some(2));
+}
@@ -0,0 +1,149 @@
+use crate::{Allocator, Cursor, Global, List};
+use core::ops::{Bound, RangeBounds};
+
+/// Iterator removing and yielding a range of elements one at a time.
+///
+/// Unlike `Vec::drain()`, dropping the iterator early leaves the elements not
+/// yet yielded in the list.
+pub struct ListDrain<'a, T, A: Allocator = Global> {
+    cursor: Cursor<'a, T, A>,
+    remaining: usize,
+}
+
+impl<T, A: Allocator> List<T, A> {
+    /// Returns an iterator removing and yielding the elements in `range` in
+    /// O(range.start) plus O(1) per element.
+    ///
+    /// Panics if the range is decreasing or goes past the end of the list.
+    pub fn drain<R>(&mut self, range: R) -> ListDrain<'_, T, A>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start
+                .checked_add(1)
+                .expect("Cannot drain past the end of the list"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end
+                .checked_add(1)
+                .expect("Cannot drain past the end of the list"),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end, "Cannot drain a decreasing range");
+        assert!(end <= self.len, "Cannot drain past the end of the list");
+        let mut cursor = self.cursor();
+        cursor.nth(start);
+        ListDrain {
+            cursor,
+            remaining: end - start,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> Cursor<'a, T, A> {
+    /// Returns an iterator removing and yielding the `count` elements after the
+    /// cursor, O(1) per element.
+    ///
+    /// The cursor does not move.
+    pub fn drain(&mut self, count: usize) -> ListDrain<'_, T, A> {
+        let remaining = count.min(self.len());
+        ListDrain {
+            cursor: self.checkpoint(),
+            remaining,
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> Iterator for ListDrain<'a, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.cursor.remove()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T, A: Allocator> ExactSizeIterator for ListDrain<'a, T, A> {}
+
+#[test]
+fn drain() {
+    let mut l = (0..10).collect::<List<_>>();
+    assert_eq!(l.drain(2..5).collect::<Vec<_>>(), [2, 3, 4]);
+    l.check_links();
+    assert_eq!(l, [0, 1, 5, 6, 7, 8, 9].iter().cloned().collect());
+
+    {
+        let mut d = l.drain(4..);
+        assert_eq!(d.len(), 3);
+        assert_eq!(d.next(), Some(7));
+        assert_eq!(d.len(), 2);
+    }
+    l.check_links();
+    assert_eq!(l, [0, 1, 5, 6, 8, 9].iter().cloned().collect());
+
+    assert_eq!(l.drain(..=1).count(), 2);
+    assert_eq!(l.drain(4..4).count(), 0);
+    l.check_links();
+    assert_eq!(l, [5, 6, 8, 9].iter().cloned().collect());
+    assert_eq!(l.drain(..).count(), 4);
+    l.check_links();
+    assert!(l.is_empty());
+}
+
+#[test]
+fn cursor_drain() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut l = (0..10).collect::<List<_>>();
+    {
+        let mut c = l.cursor();
+        c.nth(7);
+        assert_eq!(c.drain(5).collect::<Vec<_>>(), [7, 8, 9]);
+        assert_eq!(c.position(), 7);
+        assert_eq!(c.len(), 0);
+        c.rewind();
+        c.advance();
+        let r = catch_unwind(AssertUnwindSafe(|| {
+            for v in c.drain(4) {
+                assert!(v < 3);
+            }
+        }));
+        assert!(r.is_err());
+        assert_eq!(c.len(), 3);
+        assert_eq!(c.value(), Some(&4));
+    }
+    l.check_links();
+    assert_eq!(l, [0, 4, 5, 6].iter().cloned().collect());
+}
+
+#[test]
+#[should_panic]
+fn drain_panic() {
+    let mut l = (0..10).collect::<List<_>>();
+    l.drain(5..11);
+}
+
+#[test]
+#[should_panic(expected = "past the end")]
+fn drain_overflow_start() {
+    let mut l = (0..10).collect::<List<_>>();
+    l.drain((Bound::Excluded(usize::MAX), Bound::Unbounded));
+}
+
+#[test]
+#[should_panic(expected = "past the end")]
+fn drain_overflow_end() {
+    let mut l = (0..10).collect::<List<_>>();
+    l.drain(..=usize::MAX);
+}
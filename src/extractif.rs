@@ -0,0 +1,67 @@
+use crate::{Allocator, Cursor, Global, List};
+
+/// Iterator removing and yielding the elements matching a predicate.
+///
+/// Elements are only visited when the iterator is advanced. Dropping it early
+/// leaves the remaining elements in the list.
+pub struct ListExtractIf<'a, T, F, A: Allocator = Global> {
+    cursor: Cursor<'a, T, A>,
+    pred: F,
+}
+
+impl<T, A: Allocator> List<T, A> {
+    /// Returns an iterator removing and yielding every element for which
+    /// `pred` returns `true`.
+    ///
+    /// The elements not matching `pred` stay in the list, in the same order.
+    pub fn extract_if<F>(&mut self, pred: F) -> ListExtractIf<'_, T, F, A>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ListExtractIf {
+            cursor: self.cursor(),
+            pred,
+        }
+    }
+}
+
+impl<'a, T, F, A: Allocator> Iterator for ListExtractIf<'a, T, F, A>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let extract = (self.pred)(self.cursor.value_mut()?);
+            if extract {
+                return self.cursor.remove();
+            }
+            self.cursor.advance();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.cursor.len()))
+    }
+}
+
+#[test]
+fn extract_if() {
+    let mut l = (0..10).collect::<List<_>>();
+    let extracted = l.extract_if(|v| *v % 3 == 0).collect::<Vec<_>>();
+    assert_eq!(extracted, [0, 3, 6, 9]);
+    l.check_links();
+    assert_eq!(l, [1, 2, 4, 5, 7, 8].iter().cloned().collect());
+
+    {
+        let mut it = l.extract_if(|v| *v > 3);
+        assert_eq!(it.next(), Some(4));
+    }
+    l.check_links();
+    assert_eq!(l, [1, 2, 5, 7, 8].iter().cloned().collect());
+
+    assert_eq!(l.extract_if(|v| *v > 6).count(), 2);
+    l.check_links();
+    assert_eq!(l.back(), Some(&5));
+}
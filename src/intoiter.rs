@@ -1,12 +1,19 @@
-use crate::List;
+use crate::{Allocator, Global, List};
 
 /// Iterator consuming a list.
 #[derive(Clone)]
-pub struct ListIntoIter<T> {
-    list: List<T>,
+pub struct ListIntoIter<T, A: Allocator = Global> {
+    list: List<T, A>,
 }
 
-impl<T> Iterator for ListIntoIter<T> {
+impl<T, A: Allocator> ListIntoIter<T, A> {
+    /// Returns the list of the elements not yet consumed.
+    pub fn into_list(self) -> List<T, A> {
+        self.list
+    }
+}
+
+impl<T, A: Allocator> Iterator for ListIntoIter<T, A> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
         self.list.pop_front()
@@ -17,12 +24,12 @@ impl<T> Iterator for ListIntoIter<T> {
     }
 }
 
-impl<T> ExactSizeIterator for ListIntoIter<T> {}
+impl<T, A: Allocator> ExactSizeIterator for ListIntoIter<T, A> {}
 
 /// `for v in my_list { v ... }`
-impl<T> IntoIterator for List<T> {
+impl<T, A: Allocator> IntoIterator for List<T, A> {
     type Item = T;
-    type IntoIter = ListIntoIter<Self::Item>;
+    type IntoIter = ListIntoIter<T, A>;
 
     fn into_iter(self) -> Self::IntoIter {
         ListIntoIter { list: self }
@@ -42,3 +49,12 @@ fn into_iter() {
     }
     assert_eq!(acc, 45);
 }
+
+#[test]
+fn into_iter_into_list() {
+    let mut iter = (0..10).collect::<List<_>>().into_iter();
+    iter.nth(3);
+    let l = iter.into_list();
+    l.check_links();
+    assert_eq!(l, (4..10).collect());
+}
@@ -1,34 +1,37 @@
-use crate::{Link, List};
+use crate::{Allocator, Global, Link, List};
 
 /// Read-only iterator over a list.
 // Can't use derive(Clone) here because it will require an extra Clone bound for
 // T which we don't need.
 // #[derive(Clone)]
-pub struct ListIter<'a, T> {
-    next_link: &'a Link<T>,
-    len: usize,
+pub struct ListIter<'a, T, A: Allocator = Global> {
+    pub(crate) next_link: &'a Link<T, A>,
+    pub(crate) len: usize,
+    pub(crate) list_len: usize,
 }
 
-impl<'a, T> Clone for ListIter<'a, T> {
-    fn clone(&self) -> ListIter<'a, T> {
+impl<'a, T, A: Allocator> Clone for ListIter<'a, T, A> {
+    fn clone(&self) -> ListIter<'a, T, A> {
         ListIter {
             next_link: self.next_link,
             len: self.len,
+            list_len: self.list_len,
         }
     }
 }
 
-impl<T> List<T> {
+impl<T, A: Allocator> List<T, A> {
     /// Returns an iterator over the list yielding read-only references.
-    pub fn iter(&self) -> ListIter<'_, T> {
+    pub fn iter(&self) -> ListIter<'_, T, A> {
         ListIter {
             next_link: &self.head,
             len: self.len,
+            list_len: self.len,
         }
     }
 }
 
-impl<'a, T> Iterator for ListIter<'a, T> {
+impl<'a, T, A: Allocator> Iterator for ListIter<'a, T, A> {
     type Item = &'a T;
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(ref node) = *self.next_link {
@@ -46,12 +49,12 @@ impl<'a, T> Iterator for ListIter<'a, T> {
     }
 }
 
-impl<'a, T> ExactSizeIterator for ListIter<'a, T> {}
+impl<'a, T, A: Allocator> ExactSizeIterator for ListIter<'a, T, A> {}
 
 /// `for v in &my_list { *v ... }`
-impl<'a, T> IntoIterator for &'a List<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a List<T, A> {
     type Item = &'a T;
-    type IntoIter = ListIter<'a, T>;
+    type IntoIter = ListIter<'a, T, A>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
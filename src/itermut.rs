@@ -1,33 +1,48 @@
-use crate::{Link, List};
+use crate::{Allocator, Global, Link, List, Node, Pool};
+use core::ptr;
 
 mod extra;
 
 /// Mutable iterator over a list.
-pub struct ListIterMut<'a, T> {
-    next_link: &'a mut Link<T>,
+pub struct ListIterMut<'a, T, A: Allocator = Global> {
+    next_link: &'a mut Link<T, A>,
     len: usize,
     list_len: &'a mut usize,
+    list_tail: &'a mut *mut Node<T, A>,
+    list_pool: &'a mut Pool<T, A>,
+    list_alloc: &'a A,
+    // The node owning `next_link`, null when `next_link` is the list head.
+    prev_node: *mut Node<T, A>,
 }
 
-impl<T> List<T> {
+unsafe impl<'a, T: Send, A: Allocator + Send + Sync> Send for ListIterMut<'a, T, A> {}
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Sync for ListIterMut<'a, T, A> {}
+
+impl<T, A: Allocator> List<T, A> {
     /// Returns an iterator over the list yielding mutable references.
-    pub fn iter_mut(&mut self) -> ListIterMut<'_, T> {
+    pub fn iter_mut(&mut self) -> ListIterMut<'_, T, A> {
         ListIterMut {
             len: self.len,
             list_len: &mut self.len,
+            list_tail: &mut self.tail,
+            list_pool: &mut self.pool,
+            list_alloc: &self.alloc,
+            prev_node: ptr::null_mut(),
             next_link: &mut self.head,
         }
     }
 }
 
-impl<'a, T> Iterator for ListIterMut<'a, T> {
+impl<'a, T, A: Allocator> Iterator for ListIterMut<'a, T, A> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
         let next_link: *mut _ = self.next_link;
         unsafe {
             if let Some(ref mut node) = *next_link {
-                let (value, next) = node.take_mut();
+                let node = node.as_ptr();
+                self.prev_node = node;
+                let (value, next) = (*node).take_mut();
                 self.next_link = next;
                 self.len -= 1;
                 Some(value)
@@ -43,12 +58,12 @@ impl<'a, T> Iterator for ListIterMut<'a, T> {
     }
 }
 
-impl<'a, T> ExactSizeIterator for ListIterMut<'a, T> {}
+impl<'a, T, A: Allocator> ExactSizeIterator for ListIterMut<'a, T, A> {}
 
 /// `for v in &mut my_list { *v = ... }`
-impl<'a, T> IntoIterator for &'a mut List<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a mut List<T, A> {
     type Item = &'a mut T;
-    type IntoIter = ListIterMut<'a, T>;
+    type IntoIter = ListIterMut<'a, T, A>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter_mut()
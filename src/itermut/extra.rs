@@ -1,9 +1,10 @@
 use super::ListIterMut;
-use crate::{Cursor, List, Node};
-use std::mem;
+use crate::{Allocator, Cursor, CursorOrigin, List, NodeBox};
+use core::mem;
+use core::ptr;
 
 /// Extra operations on mutable iterator - **Unstable API**.
-impl<'a, T> ListIterMut<'a, T> {
+impl<'a, T, A: Allocator> ListIterMut<'a, T, A> {
     /// Returns a reference to the next element, without moving the iterator.
     pub fn peek_next(&self) -> Option<&T> {
         self.next_link.as_ref().map(|node| &node.value)
@@ -19,12 +20,42 @@ impl<'a, T> ListIterMut<'a, T> {
     /// O(1).
     ///
     /// The inserted element does not appear in the iteration.
-    pub fn insert_next(&mut self, v: T) {
-        let new_node = Node::new_boxed(v, self.next_link.take());
+    pub fn insert_next(&mut self, v: T)
+    where
+        A: Clone,
+    {
+        let mut new_node = NodeBox::from_pool(self.list_pool, v, self.list_alloc).0;
+        new_node.next = self.next_link.take();
+        let node = new_node.as_ptr();
+        if new_node.next.is_none() {
+            *self.list_tail = node;
+        }
         *self.next_link = Some(new_node);
         *self.list_len += 1;
-        self.len += 1;
-        self.next();
+        self.prev_node = node;
+        self.next_link = unsafe { &mut (*node).next };
+    }
+
+    /// Insert all the values from `iter` just after the element most recently
+    /// returned by `.next()` in O(iter.len()).
+    /// Returns the number of inserted values.
+    ///
+    /// The inserted elements do not appear in the iteration. They are linked to
+    /// the list only once `iter` is exhausted, if it panics the list is left
+    /// untouched.
+    pub fn insert_iter_next<I>(&mut self, iter: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+        A: Clone,
+    {
+        let mut other = List::from_iter_pooled(iter, self.list_pool, self.list_alloc);
+        let count = other.len();
+        let other_tail = other.link_before(self.next_link, self.list_len, self.list_tail);
+        if !other_tail.is_null() {
+            self.prev_node = other_tail;
+            self.next_link = unsafe { &mut (*other_tail).next };
+        }
+        count
     }
 
     /// Remove the element after the one most recently returned by `.next()` in
@@ -33,12 +64,19 @@ impl<'a, T> ListIterMut<'a, T> {
     /// Returns the removed value or None if the iterator is already at the end
     /// of the list.
     pub fn remove_next(&mut self) -> Option<T> {
-        self.next_link.take().map(|node| {
-            let (value, next) = node.take();
-            *self.next_link = next;
+        let node = self.remove_next_node()?;
+        Some(node.recycle(self.list_pool))
+    }
+
+    pub(crate) fn remove_next_node(&mut self) -> Option<NodeBox<T, A>> {
+        self.next_link.take().map(|mut node| {
+            *self.next_link = node.next.take();
+            if self.next_link.is_none() {
+                *self.list_tail = self.prev_node;
+            }
             *self.list_len -= 1;
             self.len -= 1;
-            value
+            NodeBox(node)
         })
     }
 
@@ -46,26 +84,50 @@ impl<'a, T> ListIterMut<'a, T> {
     /// `.next()` in O(1).
     ///
     /// * returns a new list owning all the elements after the one most recently
-    /// returned by `.next()`.
+    ///   returned by `.next()`.
     /// * the iterator is now exhausted since the list got truncated.
     /// * returns an empty list if the iterator was already exhausted.
-    pub fn truncate_next(&mut self) -> List<T> {
+    pub fn truncate_next(&mut self) -> List<T, A>
+    where
+        A: Clone,
+    {
         let tail_link = self.next_link.take();
         *self.list_len -= self.len;
+        let tail_tail = if tail_link.is_some() {
+            mem::replace(self.list_tail, self.prev_node)
+        } else {
+            ptr::null_mut()
+        };
         List {
             len: mem::replace(&mut self.len, 0),
             head: tail_link,
+            tail: tail_tail,
+            pool: None,
+            alloc: self.list_alloc.clone(),
         }
     }
 }
 
 /// Convert the mutable iterator into a cursor **unstable* API*.
-impl<'a, T> Into<Cursor<'a, T>> for ListIterMut<'a, T> {
-    fn into(self) -> Cursor<'a, T> {
+impl<'a, T, A: Allocator> From<ListIterMut<'a, T, A>> for Cursor<'a, T, A> {
+    fn from(iter: ListIterMut<'a, T, A>) -> Cursor<'a, T, A> {
+        // The values already returned by the iterator may still be borrowed,
+        // the cursor must never rewind before them.
+        let position = *iter.list_len - iter.len;
+        let next_link: *mut _ = iter.next_link;
         Cursor {
-            position: *self.list_len - self.len,
-            list_len: self.list_len,
-            next_link: self.next_link,
+            position,
+            list_len: iter.list_len,
+            list_tail: iter.list_tail,
+            list_pool: iter.list_pool,
+            list_alloc: iter.list_alloc,
+            prev_node: iter.prev_node,
+            next_link: unsafe { &mut *next_link },
+            origin: CursorOrigin {
+                next_link,
+                prev_node: iter.prev_node,
+                position,
+            },
         }
     }
 }
@@ -106,3 +168,85 @@ fn mutref_iter_advanced() {
         assert_eq!(iter.next(), Some(i));
     }
 }
+
+#[test]
+fn mutref_iter_tail() {
+    let mut l = (0..5).collect::<List<_>>();
+    {
+        let mut iter = l.iter_mut();
+        iter.insert_next(-1);
+        for _ in &mut iter {}
+        iter.insert_next(5);
+        iter.insert_next(6);
+    }
+    l.check_links();
+    assert_eq!(l.back(), Some(&6));
+    {
+        let mut iter = l.iter_mut();
+        iter.nth(6);
+        assert_eq!(iter.remove_next(), Some(6));
+        assert_eq!(iter.remove_next(), None);
+    }
+    l.check_links();
+    assert_eq!(l.back(), Some(&5));
+    let tail = {
+        let mut iter = l.iter_mut();
+        iter.nth(2);
+        iter.truncate_next()
+    };
+    l.check_links();
+    tail.check_links();
+    assert_eq!(l.back(), Some(&1));
+    assert_eq!(tail.back(), Some(&5));
+    let tail = l.iter_mut().truncate_next();
+    l.check_links();
+    tail.check_links();
+    assert!(l.is_empty());
+    assert_eq!(tail.len(), 3);
+    {
+        let mut iter = l.iter_mut();
+        iter.insert_next(7);
+        let mut c: Cursor<'_, _> = iter.into();
+        c.insert(8);
+    }
+    l.check_links();
+    assert_eq!(l.back(), Some(&8));
+}
+
+#[test]
+fn mutref_iter_into_cursor_rewind() {
+    let mut l = (0..5).collect::<List<_>>();
+    {
+        let mut iter = l.iter_mut();
+        let first = iter.next().unwrap();
+        iter.next();
+        let mut c: Cursor<'_, _> = iter.into();
+        assert_eq!(c.position(), 2);
+        c.advance();
+        c.rewind();
+        assert_eq!(c.position(), 2);
+        assert_eq!(c.value(), Some(&2));
+        assert_eq!(c.seek(0), 2);
+        assert_eq!(c.remove(), Some(2));
+        *first += 10;
+    }
+    l.check_links();
+    assert_eq!(l, [10, 1, 3, 4].iter().cloned().collect());
+}
+
+#[test]
+fn mutref_iter_insert_iter() {
+    let mut l = (0..4).collect::<List<_>>();
+    {
+        let mut iter = l.iter_mut();
+        assert_eq!(iter.insert_iter_next(10..12), 2);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(&mut 0));
+        assert_eq!(iter.insert_iter_next(None), 0);
+        for _ in &mut iter {}
+        assert_eq!(iter.insert_iter_next(20..22), 2);
+        assert_eq!(iter.next(), None);
+    }
+    l.check_links();
+    assert_eq!(l, [10, 11, 0, 1, 2, 3, 20, 21].iter().cloned().collect());
+}
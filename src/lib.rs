@@ -26,7 +26,8 @@
 //! values of the list.
 //!
 //! For example, using cursors, we can implement a non-recursive merge-sort by
-//! sorting and merging the list by pair of 2, 4, 8, etc...
+//! sorting and merging the list by pair of 2, 4, 8, etc... This is only an
+//! example, `List::sort()` does it in place without any allocation.
 //!
 //! ```
 //! extern crate fwdlist;
@@ -38,7 +39,7 @@
 //!     i.collect::<List<_>>()
 //! }
 //!
-//! fn merge<'c, T>(mut a: List<T>, mut b: List<T>)
+//! fn merge<T>(mut a: List<T>, mut b: List<T>)
 //!     -> List<T> where T: Ord + Debug {
 //!     use std::cmp::Ordering::*;
 //!
@@ -97,30 +98,81 @@
 //! }
 //! ```
 //!
+//! # `no_std`
+//!
+//! The crate only needs `alloc`. Disable the default `std` feature to build it
+//! as `#![no_std]`.
+//!
 //! Happy hacking!
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(feature = "bench", feature(test))]
 
+extern crate alloc;
+
+pub use crate::cursor::CursorPositions;
+pub use crate::cursorref::CursorRef;
+pub use crate::drain::ListDrain;
+pub use crate::extractif::ListExtractIf;
 pub use crate::intoiter::ListIntoIter;
 pub use crate::iter::ListIter;
 pub use crate::itermut::ListIterMut;
+pub use crate::mergeall::ListMergeAll;
+pub use crate::nodepool::NodePool;
+pub use crate::ownedcursor::OwnedCursor;
+pub use allocator_api2::alloc::{AllocError, Allocator, Global};
+
+use allocator_api2::boxed::Box;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
 
 mod cursor;
+mod cursorref;
+mod drain;
+mod extractif;
 mod intoiter;
 mod iter;
 mod itermut;
+mod mergeall;
+mod nodebox;
+mod nodepool;
 mod ops;
+mod ownedcursor;
+pub mod sorted;
 
 /// A simply linked list.
-pub struct List<T> {
+///
+/// Nodes are allocated with `A`, the global allocator by default. See
+/// `List::new_in()`.
+///
+/// Every node keeps a copy of the allocator it was allocated with, and is
+/// freed through it. Nodes move freely between lists (`append()`, `splice()`,
+/// `merge()`, `NodeBox`, `NodePool`...), and two lists of the same allocator
+/// type may still use distinct allocators, two arenas for example. The copy is
+/// free for a zero-sized allocator like `Global`, but costs the size of `A` per
+/// node otherwise: a pointer for a `&Arena`. This is also why the operations
+/// allocating nodes or creating new lists require `A: Clone`.
+pub struct List<T, A: Allocator = Global> {
     len: usize,
-    head: Link<T>,
+    head: Link<T, A>,
+    // The last node of the list, null when the list is empty. It makes every
+    // operation at the back of the list O(1).
+    tail: *mut Node<T, A>,
+    pool: Pool<T, A>,
+    alloc: A,
 }
 
+// The tail pointer is only an alias to a node owned by the list itself.
+unsafe impl<T: Send, A: Allocator + Send> Send for List<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for List<T, A> {}
+
 /// A cursor to navigate the list and reshape it.
 ///
 /// Conceptually, a cursor moves between nodes, think the cursor of your text
-/// editor.
+/// editor. It moves forward, and can only move backward by going back to
+/// where it started from with `rewind()` or `seek()`.
 ///
 /// ## Cursor by example
 ///
@@ -154,30 +206,165 @@ pub struct List<T> {
 ///
 /// With a cursor, you can truncate the list, insert and removes nodes, etc.
 ///
-pub struct Cursor<'a, T> {
-    next_link: &'a mut Link<T>,
+/// # Threads
+///
+/// A cursor owns the nodes it removes, so sending it to another thread requires
+/// the allocator to be `Send` as well as `Sync`:
+///
+/// ```compile_fail
+/// use fwdlist::{AllocError, Allocator, Global, List};
+/// use std::alloc::Layout;
+/// use std::marker::PhantomData;
+/// use std::ptr::NonNull;
+///
+/// struct NotSend(PhantomData<*const ()>);
+/// unsafe impl Sync for NotSend {}
+///
+/// unsafe impl Allocator for NotSend {
+///     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+///         Global.allocate(layout)
+///     }
+///     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+///         Global.deallocate(ptr, layout)
+///     }
+/// }
+///
+/// fn assert_send<T: Send>(_: T) {}
+///
+/// let mut l = List::<u8, _>::new_in(NotSend(PhantomData));
+/// assert_send(l.cursor());
+/// ```
+pub struct Cursor<'a, T, A: Allocator = Global> {
+    next_link: &'a mut Link<T, A>,
     list_len: &'a mut usize,
+    list_tail: &'a mut *mut Node<T, A>,
+    list_pool: &'a mut Pool<T, A>,
+    list_alloc: &'a A,
+    // The node owning `next_link`, null when `next_link` is the list head.
+    prev_node: *mut Node<T, A>,
+    position: usize,
+    origin: CursorOrigin<T, A>,
+}
+
+// Where a cursor goes back to on `rewind()`: the list head, or the position of
+// the cursor it was checkpointed from.
+struct CursorOrigin<T, A: Allocator> {
+    next_link: *mut Link<T, A>,
+    prev_node: *mut Node<T, A>,
     position: usize,
 }
 
-type Link<T> = Option<Box<Node<T>>>;
+// Can't use derive(Clone, Copy) here because it will require an extra Clone
+// bound for T which we don't need.
+impl<T, A: Allocator> Clone for CursorOrigin<T, A> {
+    fn clone(&self) -> CursorOrigin<T, A> {
+        *self
+    }
+}
+
+impl<T, A: Allocator> Copy for CursorOrigin<T, A> {}
+
+impl<T, A: Allocator> CursorOrigin<T, A> {
+    fn head(head: *mut Link<T, A>) -> CursorOrigin<T, A> {
+        CursorOrigin {
+            next_link: head,
+            prev_node: ptr::null_mut(),
+            position: 0,
+        }
+    }
+}
+
+unsafe impl<'a, T: Send, A: Allocator + Send + Sync> Send for Cursor<'a, T, A> {}
+unsafe impl<'a, T: Sync, A: Allocator + Sync> Sync for Cursor<'a, T, A> {}
 
-#[derive(Debug)]
-struct Node<T> {
+/// A node detached from any list, owning its value.
+///
+/// Moving a `NodeBox` from a list to another relinks it, without any
+/// allocation. See `List::pop_front_node()` and `List::push_front_node()`.
+pub struct NodeBox<T, A: Allocator = Global>(NodePtr<T, A>);
+
+type Link<T, A> = Option<NodePtr<T, A>>;
+type Pool<T, A> = Option<Box<NodePool<T, A>>>;
+
+struct Node<T, A: Allocator> {
     value: T,
-    next: Link<T>,
+    next: Link<T, A>,
+}
+
+// An owning pointer to a node, freed with its own copy of the allocator.
+//
+// Unlike `Box`, `as_ptr()` gives away the pointer without borrowing the node
+// first. The raw pointers kept to nodes (the tail of the list, the node before
+// a cursor...) stay valid however the node is borrowed through its link later.
+struct NodePtr<T, A: Allocator> {
+    ptr: NonNull<Node<T, A>>,
+    alloc: ManuallyDrop<A>,
+    _owns: PhantomData<Node<T, A>>,
+}
+
+unsafe impl<T: Send, A: Allocator + Send> Send for NodePtr<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for NodePtr<T, A> {}
+
+impl<T, A: Allocator> NodePtr<T, A> {
+    fn from_box(node: Box<Node<T, A>, A>) -> NodePtr<T, A> {
+        let (ptr, alloc) = Box::into_raw_with_allocator(node);
+        NodePtr {
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            alloc: ManuallyDrop::new(alloc),
+            _owns: PhantomData,
+        }
+    }
+
+    fn into_box(self) -> Box<Node<T, A>, A> {
+        let mut node = ManuallyDrop::new(self);
+        unsafe {
+            let alloc = ManuallyDrop::take(&mut node.alloc);
+            Box::from_raw_in(node.ptr.as_ptr(), alloc)
+        }
+    }
+
+    fn as_ptr(&self) -> *mut Node<T, A> {
+        self.ptr.as_ptr()
+    }
+
+    fn into_inner(self) -> Node<T, A> {
+        Box::into_inner(self.into_box())
+    }
+}
+
+impl<T, A: Allocator> Deref for NodePtr<T, A> {
+    type Target = Node<T, A>;
+
+    fn deref(&self) -> &Node<T, A> {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T, A: Allocator> DerefMut for NodePtr<T, A> {
+    fn deref_mut(&mut self) -> &mut Node<T, A> {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T, A: Allocator> Drop for NodePtr<T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            let alloc = ManuallyDrop::take(&mut self.alloc);
+            drop(Box::from_raw_in(self.ptr.as_ptr(), alloc));
+        }
+    }
 }
 
-impl<T> Node<T> {
-    fn new_boxed(value: T, next: Link<T>) -> Box<Node<T>> {
-        Box::new(Node { value, next })
+impl<T, A: Allocator> Node<T, A> {
+    fn new_boxed(value: T, next: Link<T, A>, alloc: A) -> NodePtr<T, A> {
+        NodePtr::from_box(Box::new_in(Node { value, next }, alloc))
     }
 
-    fn take(self) -> (T, Link<T>) {
-        (self.value, self.next)
+    fn try_new_boxed(value: T, next: Link<T, A>, alloc: A) -> Result<NodePtr<T, A>, AllocError> {
+        Box::try_new_in(Node { value, next }, alloc).map(NodePtr::from_box)
     }
 
-    fn take_mut(&mut self) -> (&mut T, &mut Link<T>) {
+    fn take_mut(&mut self) -> (&mut T, &mut Link<T, A>) {
         (&mut self.value, &mut self.next)
     }
 }
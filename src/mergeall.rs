@@ -0,0 +1,198 @@
+use crate::{Allocator, Global, List, NodeBox};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Iterator merging many sorted lists, consuming them.
+///
+/// Uses a binary heap over the lists, so yielding `n` elements out of `k` lists
+/// is O(n * log(k)).
+pub struct ListMergeAll<T, F, A: Allocator = Global> {
+    // Lists are tagged with their rank in the input, to keep the merge stable.
+    heap: Vec<(usize, List<T, A>)>,
+    compare: F,
+    len: usize,
+}
+
+impl<T: Ord, A: Allocator> List<T, A> {
+    /// Merge all the sorted `lists` into a single list in O(n * log(k)).
+    ///
+    /// The merge is stable, on ties elements from the first lists come first.
+    /// Nodes are relinked, no value is moved nor reallocated.
+    ///
+    /// The result uses the allocator of the first list, or `A::default()` if
+    /// there is none.
+    pub fn merge_all<I>(lists: I) -> List<T, A>
+    where
+        I: IntoIterator<Item = List<T, A>>,
+        A: Clone + Default,
+    {
+        List::merge_all_by(lists, T::cmp)
+    }
+
+    /// Returns an iterator yielding the values of all the sorted `lists` in
+    /// order.
+    ///
+    /// See `merge_all()`.
+    pub fn merge_all_iter<I>(lists: I) -> ListMergeAll<T, fn(&T, &T) -> Ordering, A>
+    where
+        I: IntoIterator<Item = List<T, A>>,
+    {
+        List::merge_all_iter_by(lists, T::cmp)
+    }
+}
+
+impl<T, A: Allocator> List<T, A> {
+    /// Merge all the `lists`, sorted with the comparator function `compare`,
+    /// into a single list.
+    ///
+    /// See `merge_all()`.
+    pub fn merge_all_by<I, F>(lists: I, compare: F) -> List<T, A>
+    where
+        I: IntoIterator<Item = List<T, A>>,
+        F: FnMut(&T, &T) -> Ordering,
+        A: Clone + Default,
+    {
+        let mut lists = lists.into_iter().peekable();
+        let alloc = lists.peek().map_or_else(A::default, |l| l.alloc.clone());
+        let mut merger = List::merge_all_iter_by(lists, compare);
+        let mut r = List::new_in(alloc);
+        while merger.heap.len() > 1 {
+            r.push_back_node(merger.pop_node().unwrap());
+        }
+        if let Some((_, mut last)) = merger.heap.pop() {
+            r.append(&mut last);
+        }
+        r
+    }
+
+    /// Returns an iterator yielding the values of all the `lists`, sorted with
+    /// the comparator function `compare`, in order.
+    ///
+    /// See `merge_all()`.
+    pub fn merge_all_iter_by<I, F>(lists: I, compare: F) -> ListMergeAll<T, F, A>
+    where
+        I: IntoIterator<Item = List<T, A>>,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let heap = lists
+            .into_iter()
+            .filter(|l| !l.is_empty())
+            .enumerate()
+            .collect::<Vec<_>>();
+        let len = heap.iter().map(|(_, l)| l.len()).sum();
+        let mut merger = ListMergeAll { heap, compare, len };
+        for i in (0..merger.heap.len() / 2).rev() {
+            merger.sift_down(i);
+        }
+        merger
+    }
+}
+
+impl<T, F, A: Allocator> ListMergeAll<T, F, A>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    fn is_less(&mut self, i: usize, j: usize) -> bool {
+        let (ref rank_a, ref a) = self.heap[i];
+        let (ref rank_b, ref b) = self.heap[j];
+        match (self.compare)(a.front().unwrap(), b.front().unwrap()) {
+            Ordering::Equal => rank_a < rank_b,
+            order => order == Ordering::Less,
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut min = i;
+            for child in &[2 * i + 1, 2 * i + 2] {
+                if *child < self.heap.len() && self.is_less(*child, min) {
+                    min = *child;
+                }
+            }
+            if min == i {
+                return;
+            }
+            self.heap.swap(i, min);
+            i = min;
+        }
+    }
+
+    fn pop_node(&mut self) -> Option<NodeBox<T, A>> {
+        let node = self.heap.first_mut()?.1.pop_front_node();
+        self.len -= 1;
+        if self.heap[0].1.is_empty() {
+            self.heap.swap_remove(0);
+        }
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        node
+    }
+}
+
+impl<T, F, A: Allocator> Iterator for ListMergeAll<T, F, A>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pop_node().map(NodeBox::into_inner)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, F, A: Allocator> ExactSizeIterator for ListMergeAll<T, F, A> where
+    F: FnMut(&T, &T) -> Ordering
+{
+}
+
+#[cfg(test)]
+fn mkruns() -> Vec<List<(u32, usize)>> {
+    (0..7)
+        .map(|run| {
+            let mut l = (0..20)
+                .map(|i| ((i * (run as u32 + 1)) % 23, run))
+                .collect::<List<_>>();
+            l.sort_by_key(|&(v, _)| v);
+            l
+        })
+        .collect()
+}
+
+#[test]
+fn merge_all() {
+    let mut expected = mkruns().into_iter().flatten().collect::<Vec<_>>();
+    expected.sort_by_key(|&(v, _)| v);
+
+    let l = List::merge_all_by(mkruns(), |a, b| a.0.cmp(&b.0));
+    l.check_links();
+    assert_eq!(l, expected.iter().cloned().collect());
+
+    let it = List::merge_all_iter_by(mkruns(), |a, b| a.0.cmp(&b.0));
+    assert_eq!(it.len(), 7 * 20);
+    assert_eq!(it.collect::<Vec<_>>(), expected);
+
+    let l = List::merge_all(vec![List::new(), (3..5).collect(), (0..4).collect()]);
+    l.check_links();
+    assert_eq!(l, [0, 1, 2, 3, 3, 4].iter().cloned().collect());
+    assert!(List::<u8>::merge_all(vec![]).is_empty());
+    assert_eq!(
+        List::merge_all_iter(vec![(0..3).collect::<List<_>>()]).count(),
+        3
+    );
+
+    let alloc = crate::ops::TestAlloc::default();
+    let mut a = List::new_in(alloc.clone());
+    a.extend([1, 4].iter().cloned());
+    let mut b = List::new_in(alloc.clone());
+    b.extend(0..3);
+    let mut l = List::merge_all(vec![a, List::new_in(alloc.clone()), b]);
+    l.check_links();
+    assert!(l.iter().eq(&[0, 1, 1, 2, 4]));
+    l.push_back(5);
+    assert_eq!(alloc.live(), 6);
+}
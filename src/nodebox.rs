@@ -0,0 +1,53 @@
+use crate::{AllocError, Allocator, Global, Node, NodeBox};
+use core::fmt;
+
+impl<T> NodeBox<T> {
+    /// Allocate a new detached node containing the value `v`.
+    pub fn new(v: T) -> NodeBox<T> {
+        NodeBox::new_in(v, Global)
+    }
+}
+
+impl<T, A: Allocator> NodeBox<T, A> {
+    /// Allocate a new detached node containing the value `v` with `alloc`.
+    pub fn new_in(v: T, alloc: A) -> NodeBox<T, A> {
+        NodeBox(Node::new_boxed(v, None, alloc))
+    }
+
+    /// Allocate a new detached node containing the value `v` with `alloc`.
+    /// Returns an error if the allocation fails.
+    pub fn try_new_in(v: T, alloc: A) -> Result<NodeBox<T, A>, AllocError> {
+        Node::try_new_boxed(v, None, alloc).map(NodeBox)
+    }
+
+    /// Free the node and return its value.
+    pub fn into_inner(self) -> T {
+        self.0.into_inner().value
+    }
+
+    /// A read-only reference to the value.
+    pub fn value(&self) -> &T {
+        &self.0.value
+    }
+
+    /// A mutable reference to the value.
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.0.value
+    }
+}
+
+/// A debug formatter.
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for NodeBox<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NodeBox").field(self.value()).finish()
+    }
+}
+
+#[test]
+fn node_box() {
+    let mut node = NodeBox::new(String::from("a"));
+    node.value_mut().push('b');
+    assert_eq!(node.value(), "ab");
+    assert_eq!(format!("{:?}", node), "NodeBox(\"ab\")");
+    assert_eq!(node.into_inner(), "ab");
+}
@@ -0,0 +1,308 @@
+use crate::{AllocError, Allocator, Global, List, Node, NodeBox, NodePtr, Pool};
+use allocator_api2::boxed::Box;
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+
+/// A cache of freed nodes, for a list to reuse instead of allocating.
+///
+/// Once attached with `List::with_node_pool()`, nodes freed by `pop_front()`,
+/// `Cursor::remove()` and `clear()` are kept in the pool, and reused by
+/// `push_front()`, `push_back()` and `Cursor::insert()`. The pool holds at most
+/// `max_size()` nodes, anything beyond that goes back to the allocator. Lists
+/// split off from a pooled list have no pool.
+///
+/// ```
+/// use fwdlist::{List, NodePool};
+///
+/// let mut l = List::with_node_pool(NodePool::new(16));
+/// l.push_back(1);
+/// l.pop_front();
+/// l.push_back(2);
+/// let pool = l.node_pool().unwrap();
+/// assert_eq!((pool.hits(), pool.misses()), (1, 1));
+/// ```
+pub struct NodePool<T, A: Allocator = Global> {
+    // The cached nodes, uninitialized but for the `FreeNode` written at their
+    // start to chain them. Each node keeps its allocator, so a pool can be
+    // handed over to any list, and caching a node never allocates.
+    free: FreeLink<T, A>,
+    len: usize,
+    max_size: usize,
+    hits: usize,
+    misses: usize,
+}
+
+type FreeLink<T, A> = Option<Box<MaybeUninit<Node<T, A>>, A>>;
+
+// Written in place at the start of a cached node. It is no larger than the
+// `next` link of a node, so it always fits.
+struct FreeNode<T, A: Allocator> {
+    next: FreeLink<T, A>,
+}
+
+impl<T, A: Allocator> NodePool<T, A> {
+    /// A new empty pool, caching at most `max_size` nodes.
+    pub fn new(max_size: usize) -> NodePool<T, A> {
+        NodePool {
+            free: None,
+            len: 0,
+            max_size,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The number of nodes cached in the pool.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no node is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The maximum number of nodes the pool will cache.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Change the maximum number of nodes to cache, freeing any cached node
+    /// in excess.
+    pub fn set_max_size(&mut self, max_size: usize) {
+        self.max_size = max_size;
+        while self.len > max_size {
+            self.pop();
+        }
+    }
+
+    /// Free every cached node, giving the memory back to the allocator.
+    pub fn shrink(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// How many nodes were reused from the pool.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// How many nodes had to be allocated because the pool was empty.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    fn pop(&mut self) -> Option<Box<MaybeUninit<Node<T, A>>, A>> {
+        let mut node = self.free.take()?;
+        let free = node.as_mut_ptr() as *mut FreeNode<T, A>;
+        self.free = unsafe { ptr::read(free).next };
+        self.len -= 1;
+        Some(node)
+    }
+
+    fn take(&mut self) -> Option<Box<MaybeUninit<Node<T, A>>, A>> {
+        let node = self.pop();
+        if node.is_some() {
+            self.hits += 1;
+        }
+        node
+    }
+
+    // Record a node allocated while the pool was empty.
+    fn count_miss(pool: &mut Pool<T, A>) {
+        if let Some(ref mut pool) = *pool {
+            pool.misses += 1;
+        }
+    }
+
+    fn give(&mut self, node: Box<Node<T, A>, A>) -> T {
+        if self.len >= self.max_size {
+            return Box::into_inner(node).value;
+        }
+        debug_assert!(mem::size_of::<FreeNode<T, A>>() <= mem::size_of::<Node<T, A>>());
+        let (node, alloc) = Box::into_raw_with_allocator(node);
+        let Node { value, next } = unsafe { ptr::read(node) };
+        drop(next);
+        let mut node = unsafe { Box::from_raw_in(node as *mut MaybeUninit<_>, alloc) };
+        let free = node.as_mut_ptr() as *mut FreeNode<T, A>;
+        unsafe {
+            ptr::write(
+                free,
+                FreeNode {
+                    next: self.free.take(),
+                },
+            );
+        }
+        self.free = Some(node);
+        self.len += 1;
+        value
+    }
+}
+
+impl<T, A: Allocator> Drop for NodePool<T, A> {
+    fn drop(&mut self) {
+        self.shrink();
+    }
+}
+
+impl<T, A: Allocator> NodeBox<T, A> {
+    /// A new detached node containing `v`, reusing a node from `pool` if
+    /// possible, or allocating with `alloc` otherwise.
+    pub(crate) fn from_pool(pool: &mut Pool<T, A>, v: T, alloc: &A) -> NodeBox<T, A>
+    where
+        A: Clone,
+    {
+        if let Some(node) = pool.as_mut().and_then(|pool| pool.take()) {
+            return NodeBox::reuse(node, v);
+        }
+        let node = NodeBox::new_in(v, alloc.clone());
+        NodePool::count_miss(pool);
+        node
+    }
+
+    /// Same as `from_pool()`, but returns an error if the allocation fails.
+    pub(crate) fn try_from_pool(
+        pool: &mut Pool<T, A>,
+        v: T,
+        alloc: &A,
+    ) -> Result<NodeBox<T, A>, AllocError>
+    where
+        A: Clone,
+    {
+        if let Some(node) = pool.as_mut().and_then(|pool| pool.take()) {
+            return Ok(NodeBox::reuse(node, v));
+        }
+        let node = NodeBox::try_new_in(v, alloc.clone())?;
+        NodePool::count_miss(pool);
+        Ok(node)
+    }
+
+    fn reuse(node: Box<MaybeUninit<Node<T, A>>, A>, value: T) -> NodeBox<T, A> {
+        let node = Box::write(node, Node { value, next: None });
+        NodeBox(NodePtr::from_box(node))
+    }
+
+    /// Return the value of the node, caching the node in `pool` if possible.
+    pub(crate) fn recycle(self, pool: &mut Pool<T, A>) -> T {
+        match *pool {
+            Some(ref mut pool) => pool.give(self.0.into_box()),
+            None => self.into_inner(),
+        }
+    }
+}
+
+impl<T> List<T> {
+    /// A new empty list, caching its freed nodes in `pool`.
+    pub fn with_node_pool(pool: NodePool<T>) -> List<T> {
+        let mut l = List::new();
+        l.pool = Some(Box::new(pool));
+        l
+    }
+}
+
+impl<T, A: Allocator> List<T, A> {
+    /// The node pool of the list, if any.
+    pub fn node_pool(&self) -> Option<&NodePool<T, A>> {
+        self.pool.as_deref()
+    }
+
+    /// A mutable reference to the node pool of the list, if any.
+    pub fn node_pool_mut(&mut self) -> Option<&mut NodePool<T, A>> {
+        self.pool.as_deref_mut()
+    }
+
+    /// Attach `pool` to the list, returning the previous pool if any.
+    pub fn set_node_pool(&mut self, pool: NodePool<T, A>) -> Option<NodePool<T, A>> {
+        self.pool.replace(Box::new(pool)).map(Box::into_inner)
+    }
+
+    /// Detach the node pool from the list, to hand it over to another list.
+    pub fn take_node_pool(&mut self) -> Option<NodePool<T, A>> {
+        self.pool.take().map(Box::into_inner)
+    }
+}
+
+#[test]
+fn reuse() {
+    let mut l = List::with_node_pool(NodePool::new(2));
+    l.extend(0..4);
+    assert_eq!(l.node_pool().unwrap().misses(), 4);
+    assert_eq!(l.pop_front(), Some(0));
+    assert_eq!(l.cursor().remove(), Some(1));
+    l.check_links();
+    l.clear();
+    l.check_links();
+    {
+        let pool = l.node_pool().unwrap();
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.hits(), 0);
+    }
+    l.push_front(1);
+    l.cursor().insert(5);
+    l.push_back(6);
+    l.check_links();
+    assert_eq!(l, [5, 1, 6].iter().cloned().collect());
+    let pool = l.node_pool().unwrap();
+    assert_eq!((pool.len(), pool.hits(), pool.misses()), (0, 2, 5));
+}
+
+#[test]
+fn iter_mut() {
+    let mut l = List::with_node_pool(NodePool::new(4));
+    l.extend(0..3);
+    {
+        let mut iter = l.iter_mut();
+        iter.next();
+        assert_eq!(iter.remove_next(), Some(1));
+        iter.insert_next(5);
+    }
+    l.check_links();
+    assert_eq!(l, [0, 5, 2].iter().cloned().collect());
+    let pool = l.node_pool().unwrap();
+    assert_eq!((pool.len(), pool.hits(), pool.misses()), (0, 1, 3));
+
+    l.clone_from(&(0..1).collect());
+    l.check_links();
+    assert_eq!(l, (0..1).collect());
+    assert_eq!(l.node_pool().unwrap().len(), 2);
+    l.clone_from(&(0..4).collect());
+    l.check_links();
+    assert_eq!(l, (0..4).collect());
+    let pool = l.node_pool().unwrap();
+    assert_eq!((pool.len(), pool.hits(), pool.misses()), (0, 3, 4));
+}
+
+#[test]
+fn shrink() {
+    let mut l = List::with_node_pool(NodePool::new(8));
+    l.extend((0..8).map(|i| i.to_string()));
+    l.clear();
+    assert_eq!(l.node_pool().unwrap().len(), 8);
+    l.node_pool_mut().unwrap().set_max_size(3);
+    assert_eq!(l.node_pool().unwrap().len(), 3);
+    let mut pool = l.take_node_pool().unwrap();
+    pool.shrink();
+    assert!(pool.is_empty());
+    assert_eq!(pool.max_size(), 3);
+    let mut other = List::new();
+    assert!(other.set_node_pool(pool).is_none());
+    other.push_back(String::from("x"));
+    other.pop_front();
+    assert_eq!(other.node_pool().unwrap().len(), 1);
+}
+
+#[test]
+fn custom_alloc() {
+    let alloc = crate::ops::TestAlloc::default();
+    let mut l = List::new_in(alloc.clone());
+    l.set_node_pool(NodePool::new(4));
+    l.extend(0..6);
+    l.clear();
+    assert_eq!((l.node_pool().unwrap().len(), alloc.live()), (4, 4));
+    alloc.set_limit(4);
+    l.extend(6..10);
+    assert!(l.try_push_back(10).is_err());
+    assert!(l.iter().eq(&[6, 7, 8, 9]));
+    l.clear();
+    drop(l.take_node_pool());
+    assert_eq!(alloc.live(), 0);
+}
@@ -1,30 +1,75 @@
-use crate::{Link, List};
-use std::ptr;
+use crate::{Allocator, Link, List, Node, NodeBox, Pool};
+use ::core::mem;
+use ::core::ptr;
 
 mod access;
 mod cmp;
 mod core;
 mod extra;
+mod sort;
 mod stdtraits;
 
-impl<T> List<T> {
-    fn last_link(&mut self) -> &mut Link<T> {
+impl<T, A: Allocator> List<T, A> {
+    fn last_link(&mut self) -> &mut Link<T, A> {
+        if self.tail.is_null() {
+            &mut self.head
+        } else {
+            unsafe { &mut (*self.tail).next }
+        }
+    }
+
+    /// Walk the list to find the last node again in O(n), for when nodes got
+    /// relinked all over the place.
+    fn recompute_tail(&mut self) {
+        self.tail = ptr::null_mut();
         let mut head_link = &mut self.head;
-        loop {
-            match *{ head_link } {
-                Some(ref mut node) => {
-                    head_link = &mut node.next;
-                }
-                ref mut nil_link @ None => {
-                    return nil_link;
-                }
+        while let Some(ref mut node) = *{ head_link } {
+            self.tail = node.as_ptr();
+            head_link = &mut node.next;
+        }
+    }
+
+    /// A new list of the values from `iter`, reusing the nodes of `pool` if
+    /// any.
+    pub(crate) fn from_iter_pooled<I>(iter: I, pool: &mut Pool<T, A>, alloc: &A) -> List<T, A>
+    where
+        I: IntoIterator<Item = T>,
+        A: Clone,
+    {
+        let mut r = List::new_in(alloc.clone());
+        for v in iter {
+            r.push_back_node(NodeBox::from_pool(pool, v, alloc));
+        }
+        r
+    }
+
+    /// Move all the nodes of the list in front of `next_link`, a link of
+    /// another list of length `list_len` and last node `list_tail`, in O(1).
+    /// Returns the last moved node, or null if the list was empty.
+    pub(crate) fn link_before(
+        &mut self,
+        next_link: &mut Link<T, A>,
+        list_len: &mut usize,
+        list_tail: &mut *mut Node<T, A>,
+    ) -> *mut Node<T, A> {
+        if self.is_empty() {
+            return ptr::null_mut();
+        }
+        *list_len += mem::replace(&mut self.len, 0);
+        let tail = mem::replace(&mut self.tail, ptr::null_mut());
+        unsafe {
+            (*tail).next = next_link.take();
+            if (*tail).next.is_none() {
+                *list_tail = tail;
             }
         }
+        *next_link = self.head.take();
+        tail
     }
 
     #[allow(dead_code)]
     //#[inline(never)] // <- if testing with callgrind.
-    fn penultimate_link_with_unsafe(&mut self) -> Option<&mut Link<T>> {
+    fn penultimate_link_with_unsafe(&mut self) -> Option<&mut Link<T, A>> {
         let mut prev_link = ptr::null_mut();
         let mut head_link: *mut _ = &mut self.head;
         unsafe {
@@ -40,8 +85,9 @@ impl<T> List<T> {
         }
     }
 
+    #[allow(dead_code)]
     //#[inline(never)] // <- if testing with callgrind.
-    fn penultimate_link(&mut self) -> Option<&mut Link<T>> {
+    fn penultimate_link(&mut self) -> Option<&mut Link<T, A>> {
         let mut head_link = &mut self.head;
         while let Some(ref mut node) = *{ head_link } {
             let found_last_node = {
@@ -61,6 +107,73 @@ impl<T> List<T> {
     }
 }
 
+#[cfg(test)]
+impl<T, A: Allocator> List<T, A> {
+    /// Walk the whole list and check that `len` and `tail` are up to date.
+    pub(crate) fn check_links(&self) {
+        let mut len = 0;
+        let mut last: *const crate::Node<T, A> = ptr::null();
+        let mut head_link = &self.head;
+        while let Some(ref node) = *head_link {
+            len += 1;
+            last = &**node;
+            head_link = &node.next;
+        }
+        assert_eq!(self.len, len, "stale len");
+        assert_eq!(self.tail as *const _, last, "stale tail");
+    }
+}
+
+/// An allocator counting the live allocations made through it and its clones,
+/// and failing past a limit.
+#[cfg(test)]
+#[derive(Clone)]
+pub(crate) struct TestAlloc {
+    live: std::rc::Rc<std::cell::Cell<usize>>,
+    limit: std::rc::Rc<std::cell::Cell<usize>>,
+}
+
+#[cfg(test)]
+impl Default for TestAlloc {
+    fn default() -> TestAlloc {
+        TestAlloc {
+            live: Default::default(),
+            limit: std::rc::Rc::new(std::cell::Cell::new(usize::MAX)),
+        }
+    }
+}
+
+#[cfg(test)]
+impl TestAlloc {
+    pub(crate) fn live(&self) -> usize {
+        self.live.get()
+    }
+
+    /// Fail any allocation that would make more than `limit` live ones.
+    pub(crate) fn set_limit(&self, limit: usize) {
+        self.limit.set(limit);
+    }
+}
+
+#[cfg(test)]
+unsafe impl Allocator for TestAlloc {
+    fn allocate(
+        &self,
+        layout: std::alloc::Layout,
+    ) -> Result<ptr::NonNull<[u8]>, crate::AllocError> {
+        if self.live.get() >= self.limit.get() {
+            return Err(crate::AllocError);
+        }
+        self.live.set(self.live.get() + 1);
+        crate::Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: ptr::NonNull<u8>, layout: std::alloc::Layout) {
+        self.live.set(self.live.get() - 1);
+        crate::Global.deallocate(ptr, layout)
+    }
+}
+
 #[cfg(feature = "bench")]
 mod benchs {
     extern crate test;
@@ -1,9 +1,9 @@
-use crate::List;
-use std::cmp::Ordering;
-use std::cmp::Ordering::*;
+use crate::{Allocator, List};
+use core::cmp::Ordering;
+use core::cmp::Ordering::*;
 
-impl<A: PartialEq> PartialEq for List<A> {
-    fn eq(&self, other: &List<A>) -> bool {
+impl<T: PartialEq, A: Allocator> PartialEq for List<T, A> {
+    fn eq(&self, other: &List<T, A>) -> bool {
         self.len() == other.len() && {
             for (a, b) in self.iter().zip(other.iter()) {
                 if a != b {
@@ -15,17 +15,17 @@ impl<A: PartialEq> PartialEq for List<A> {
     }
 }
 
-impl<A: Eq> Eq for List<A> {}
+impl<T: Eq, A: Allocator> Eq for List<T, A> {}
 
-impl<A: PartialOrd> PartialOrd for List<A> {
-    fn partial_cmp(&self, other: &List<A>) -> Option<Ordering> {
+impl<T: PartialOrd, A: Allocator> PartialOrd for List<T, A> {
+    fn partial_cmp(&self, other: &List<T, A>) -> Option<Ordering> {
         let (mut a, mut b) = (self.iter(), other.iter());
         loop {
             match (a.next(), b.next()) {
                 (None, None) => return Some(Equal),
                 (None, _) => return Some(Less),
                 (_, None) => return Some(Greater),
-                (Some(x), Some(y)) => match x.partial_cmp(&y) {
+                (Some(x), Some(y)) => match x.partial_cmp(y) {
                     Some(Equal) => (),
                     non_eq => return non_eq,
                 },
@@ -34,15 +34,15 @@ impl<A: PartialOrd> PartialOrd for List<A> {
     }
 }
 
-impl<A: Ord> Ord for List<A> {
-    fn cmp(&self, other: &List<A>) -> Ordering {
+impl<T: Ord, A: Allocator> Ord for List<T, A> {
+    fn cmp(&self, other: &List<T, A>) -> Ordering {
         let (mut a, mut b) = (self.iter(), other.iter());
         loop {
             match (a.next(), b.next()) {
                 (None, None) => return Equal,
                 (None, _) => return Less,
                 (_, None) => return Greater,
-                (Some(x), Some(y)) => match x.cmp(&y) {
+                (Some(x), Some(y)) => match x.cmp(y) {
                     Equal => (),
                     non_eq => return non_eq,
                 },
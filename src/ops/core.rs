@@ -1,9 +1,28 @@
-use crate::{List, Node};
+use crate::{AllocError, Allocator, Global, List, NodeBox};
+use core::ptr;
 
 impl<T> List<T> {
     /// A new empty list.
     pub fn new() -> List<T> {
-        List { len: 0, head: None }
+        List::new_in(Global)
+    }
+}
+
+impl<T, A: Allocator> List<T, A> {
+    /// A new empty list, allocating its nodes with `alloc`.
+    pub fn new_in(alloc: A) -> List<T, A> {
+        List {
+            len: 0,
+            head: None,
+            tail: ptr::null_mut(),
+            pool: None,
+            alloc,
+        }
+    }
+
+    /// The allocator of the list.
+    pub fn allocator(&self) -> &A {
+        &self.alloc
     }
 
     /// The size of the list in O(1).
@@ -17,52 +36,154 @@ impl<T> List<T> {
     }
 
     /// Push a new element at the front of the list in O(1).
-    /// Cannot fails, only panic!/OOM on memory exhaustion.
-    pub fn push_front(&mut self, v: T) {
-        self.head = Some(Node::new_boxed(v, self.head.take()));
-        self.len += 1;
+    /// Cannot fails, only panic!/OOM on memory exhaustion, see
+    /// `try_push_front()`.
+    pub fn push_front(&mut self, v: T)
+    where
+        A: Clone,
+    {
+        let node = NodeBox::from_pool(&mut self.pool, v, &self.alloc);
+        self.push_front_node(node);
+    }
+
+    /// Push a new element at the front of the list in O(1).
+    /// Returns an error, leaving the list unchanged, if the allocation fails.
+    pub fn try_push_front(&mut self, v: T) -> Result<(), AllocError>
+    where
+        A: Clone,
+    {
+        let node = NodeBox::try_from_pool(&mut self.pool, v, &self.alloc)?;
+        self.push_front_node(node);
+        Ok(())
     }
 
     /// Pop a element from the front of the list in O(1).
     /// Returns None if the list is empty.
     pub fn pop_front(&mut self) -> Option<T> {
-        self.head.take().map(|node| {
-            let (value, next) = node.take();
-            self.head = next;
+        let node = self.pop_front_node()?;
+        Some(node.recycle(&mut self.pool))
+    }
+
+    /// Push an element at the end of the list in O(1).
+    /// Cannot fails, only panic!/OOM on memory exhaustion, see
+    /// `try_push_back()`.
+    pub fn push_back(&mut self, v: T)
+    where
+        A: Clone,
+    {
+        let node = NodeBox::from_pool(&mut self.pool, v, &self.alloc);
+        self.push_back_node(node);
+    }
+
+    /// Push an element at the end of the list in O(1).
+    /// Returns an error, leaving the list unchanged, if the allocation fails.
+    pub fn try_push_back(&mut self, v: T) -> Result<(), AllocError>
+    where
+        A: Clone,
+    {
+        let node = NodeBox::try_from_pool(&mut self.pool, v, &self.alloc)?;
+        self.push_back_node(node);
+        Ok(())
+    }
+
+    /// Link the detached node `node` at the front of the list in O(1), without
+    /// allocating.
+    pub fn push_front_node(&mut self, node: NodeBox<T, A>) {
+        let mut node = node.0;
+        node.next = self.head.take();
+        if self.tail.is_null() {
+            self.tail = node.as_ptr();
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    /// Unlink the first node of the list in O(1), without freeing it.
+    /// Returns None if the list is empty.
+    pub fn pop_front_node(&mut self) -> Option<NodeBox<T, A>> {
+        self.head.take().map(|mut node| {
+            self.head = node.next.take();
+            if self.head.is_none() {
+                self.tail = ptr::null_mut();
+            }
             self.len -= 1;
-            value
+            NodeBox(node)
         })
     }
 
-    /// Push an element at the end of the list in O(n).
-    /// Cannot fails, only panic!/OOM on memory exhaustion.
-    pub fn push_back(&mut self, v: T) {
-        *self.last_link() = Some(Node::new_boxed(v, None));
+    /// Link the detached node `node` at the end of the list in O(1), without
+    /// allocating.
+    pub fn push_back_node(&mut self, node: NodeBox<T, A>) {
+        let mut node = node.0;
+        node.next = None;
+        let new_tail = node.as_ptr();
+        *self.last_link() = Some(node);
+        self.tail = new_tail;
         self.len += 1;
     }
 
     /// Pop an element from the end of the list in O(n).
     /// Returns None if the list is empty.
     pub fn pop_back(&mut self) -> Option<T> {
-        let last_node = {
-            if let Some(penultimate_link) = self.penultimate_link() {
-                penultimate_link.take()
-            } else {
-                return None;
-            }
-        };
-        last_node.map(|last_node| {
-            self.len -= 1;
-            last_node.value
-        })
+        let mut c = self.cursor();
+        c.last();
+        c.remove()
     }
 
     /// Clear the list in O(n).
     pub fn clear(&mut self) {
-        while let Some(node) = self.head.take() {
-            self.head = node.next;
+        while let Some(mut node) = self.head.take() {
+            self.head = node.next.take();
+            NodeBox(node).recycle(&mut self.pool);
         }
+        self.tail = ptr::null_mut();
+        self.len = 0;
     }
+
+    /// Push all the values from `iter` at the end of the list in
+    /// O(iter.len()).
+    /// Returns an error, leaving the list unchanged, if an allocation fails.
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), AllocError>
+    where
+        I: IntoIterator<Item = T>,
+        A: Clone,
+    {
+        let mut other = List::new_in(self.alloc.clone());
+        for v in iter {
+            match NodeBox::try_from_pool(&mut self.pool, v, &self.alloc) {
+                Ok(node) => other.push_back_node(node),
+                Err(err) => {
+                    while let Some(node) = other.pop_front_node() {
+                        node.recycle(&mut self.pool);
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        self.append(&mut other);
+        Ok(())
+    }
+
+    /// Clone the list in O(n).
+    /// Returns an error if an allocation fails.
+    pub fn try_clone(&self) -> Result<List<T, A>, AllocError>
+    where
+        T: Clone,
+        A: Clone,
+    {
+        let mut r = List::new_in(self.alloc.clone());
+        r.try_extend(self.iter().cloned())?;
+        Ok(r)
+    }
+}
+
+#[test]
+fn sizes() {
+    use core::mem::size_of;
+    // The default allocator takes no room: len, head, tail and pool.
+    assert_eq!(size_of::<Global>(), 0);
+    assert_eq!(size_of::<List<u64>>(), 4 * size_of::<usize>());
+    assert_eq!(size_of::<NodeBox<u64>>(), size_of::<usize>());
 }
 
 #[test]
@@ -93,3 +214,132 @@ fn basics() {
     assert_eq!(l.pop_front(), Some(50));
     assert_eq!(l.len(), 0);
 }
+
+#[test]
+fn tail() {
+    let mut l = List::new();
+    l.check_links();
+    l.push_front(1);
+    l.check_links();
+    assert_eq!(l.back(), Some(&1));
+    l.push_back(2);
+    l.check_links();
+    l.push_front(0);
+    l.check_links();
+    assert_eq!(l.back(), Some(&2));
+    assert_eq!(l.pop_back(), Some(2));
+    l.check_links();
+    assert_eq!(l.back(), Some(&1));
+    assert_eq!(l.pop_front(), Some(0));
+    l.check_links();
+    assert_eq!(l.pop_back(), Some(1));
+    l.check_links();
+    assert_eq!(l.pop_back(), None);
+    l.push_back(3);
+    l.check_links();
+    assert_eq!(l.pop_front(), Some(3));
+    l.check_links();
+    l.push_back(4);
+    l.push_back(5);
+    *l.back_mut().unwrap() = 50;
+    assert_eq!(l.back(), Some(&50));
+    l.clear();
+    l.check_links();
+    assert!(l.is_empty());
+    l.push_back(6);
+    l.check_links();
+}
+
+#[test]
+fn nodes() {
+    let mut a = (0..3).collect::<List<_>>();
+    let mut b = List::new();
+    while let Some(node) = a.pop_front_node() {
+        b.push_front_node(node);
+        a.check_links();
+        b.check_links();
+    }
+    assert_eq!(b, (0..3).rev().collect());
+    let mut node = b.pop_front_node().unwrap();
+    *node.value_mut() += 10;
+    a.push_back_node(node);
+    a.push_back_node(NodeBox::new(20));
+    a.push_front_node(b.pop_front_node().unwrap());
+    a.check_links();
+    b.check_links();
+    assert_eq!(a, [1, 12, 20].iter().cloned().collect());
+    assert_eq!(b.pop_front_node().unwrap().into_inner(), 0);
+    assert!(b.pop_front_node().is_none());
+    b.check_links();
+}
+
+#[test]
+fn allocator() {
+    let alloc = crate::ops::TestAlloc::default();
+    {
+        let mut l = List::new_in(alloc.clone());
+        l.extend(0..10);
+        assert_eq!(alloc.live(), 10);
+        let mut tail = l.split_off(5);
+        tail.push_back(10);
+        let mut rest = tail.cursor().split(3);
+        rest.push_front(11);
+        assert_eq!(alloc.live(), 12);
+        assert_eq!(l.pop_front(), Some(0));
+        assert_eq!(alloc.live(), 11);
+        l.append(&mut tail);
+        l.append(&mut rest);
+        l.check_links();
+        assert!(l.iter().eq(&[1, 2, 3, 4, 5, 6, 7, 11, 8, 9, 10]));
+        let l2 = l.clone();
+        assert_eq!(alloc.live(), 22);
+        drop(l);
+        assert_eq!(l2.len(), 11);
+    }
+    assert_eq!(alloc.live(), 0);
+}
+
+#[test]
+fn fallible() {
+    let alloc = crate::ops::TestAlloc::default();
+    let mut l = List::new_in(alloc.clone());
+    l.try_extend(0..3).unwrap();
+    alloc.set_limit(4);
+    l.try_push_front(-1).unwrap();
+    assert!(l.try_push_front(-2).is_err());
+    assert!(l.try_push_back(3).is_err());
+    assert!(l.cursor().try_insert(-2).is_err());
+    assert!(l.try_extend(3..5).is_err());
+    assert!(l.try_clone().is_err());
+    l.check_links();
+    assert!(l.iter().eq(&[-1, 0, 1, 2]));
+    assert_eq!(alloc.live(), 4);
+
+    assert_eq!(l.pop_front(), Some(-1));
+    *l.cursor().try_insert(-1).unwrap() -= 1;
+    assert!(l.iter().eq(&[-2, 0, 1, 2]));
+    alloc.set_limit(8);
+    let c = l.try_clone().unwrap();
+    assert!(c.iter().eq(l.iter()));
+    assert_eq!(alloc.live(), 8);
+
+    // A cached node does not need any allocation.
+    l.set_node_pool(crate::NodePool::new(1));
+    assert_eq!(l.pop_front(), Some(-2));
+    l.try_push_back(3).unwrap();
+    assert!(l.try_push_back(4).is_err());
+    assert!(l.iter().eq(&[0, 1, 2, 3]));
+    assert_eq!(l.node_pool().unwrap().misses(), 0);
+
+    // Nodes taken from the pool go back to it when try_extend() fails.
+    l.node_pool_mut().unwrap().set_max_size(2);
+    l.pop_front();
+    l.pop_front();
+    l.try_extend(4..6).unwrap();
+    assert!(l.iter().eq(&[2, 3, 4, 5]));
+    l.pop_front();
+    assert!(l.try_extend(6..8).is_err());
+    assert!(l.iter().eq(&[3, 4, 5]));
+    let pool = l.node_pool().unwrap();
+    assert_eq!((pool.len(), pool.hits(), pool.misses()), (1, 4, 0));
+}
@@ -1,11 +1,17 @@
-use crate::List;
-use std::mem;
+use crate::{Allocator, List};
+use alloc::vec::Vec;
+use core::mem;
+use core::ptr;
 
 /// Extra operations on the list - **Unstable API**.
-impl<T> List<T> {
-    /// Moves all elements from `other` to the end of the list in O(self.len());
-    pub fn append(&mut self, other: &mut List<T>) {
+impl<T, A: Allocator> List<T, A> {
+    /// Moves all elements from `other` to the end of the list in O(1).
+    pub fn append(&mut self, other: &mut List<T, A>) {
+        if other.is_empty() {
+            return;
+        }
         *self.last_link() = other.head.take();
+        self.tail = mem::replace(&mut other.tail, ptr::null_mut());
         self.len += mem::replace(&mut other.len, 0);
     }
 
@@ -15,31 +21,242 @@ impl<T> List<T> {
     /// * if `at == self.len()`, returns an empty list in O(1).
     /// * Panics if `at > self.len()`.
     #[inline(never)]
-    pub fn split_off(&mut self, at: usize) -> List<T> {
+    pub fn split_off(&mut self, at: usize) -> List<T, A>
+    where
+        A: Clone,
+    {
         assert!(at <= self.len, "Cannot split off at a nonexistent index");
         if at == self.len {
-            return List::new();
+            return List::new_in(self.alloc.clone());
+        }
+
+        let mut c = self.cursor();
+        c.nth(at);
+        c.truncate()
+    }
+
+    /// Reverse the list in place in O(n).
+    pub fn reverse(&mut self) {
+        let len = self.len;
+        self.cursor().reverse_next(len);
+    }
+
+    /// Rotate the list in place so that the element at `k % len` becomes the
+    /// first one, in O(k % len).
+    pub fn rotate_left(&mut self, k: usize)
+    where
+        A: Clone,
+    {
+        if self.len < 2 {
+            return;
+        }
+        let k = k % self.len;
+        if k == 0 {
+            return;
+        }
+        let mut back = {
+            let mut c = self.cursor();
+            c.nth(k);
+            c.truncate()
+        };
+        self.cursor().splice(&mut back);
+    }
+
+    /// Rotate the list in place so that the element at `len - k % len` becomes
+    /// the first one, in O(len - k % len).
+    pub fn rotate_right(&mut self, k: usize)
+    where
+        A: Clone,
+    {
+        if self.len < 2 {
+            return;
+        }
+        let k = k % self.len;
+        self.rotate_left(self.len - k);
+    }
+
+    /// Split the list in two in O(n): the elements for which `pred` returns
+    /// `true`, and the others.
+    ///
+    /// Nodes are relinked, keeping their relative order.
+    pub fn partition<F>(mut self, mut pred: F) -> (List<T, A>, List<T, A>)
+    where
+        F: FnMut(&T) -> bool,
+        A: Clone,
+    {
+        let (mut matching, mut others) = (
+            List::new_in(self.alloc.clone()),
+            List::new_in(self.alloc.clone()),
+        );
+        while let Some(v) = self.front() {
+            let dst = if pred(v) { &mut matching } else { &mut others };
+            dst.push_back_node(self.pop_front_node().unwrap());
+        }
+        (matching, others)
+    }
+
+    /// Split the list in `n` lists in O(n), the element `v` going to the list
+    /// at index `key(v)`.
+    ///
+    /// Nodes are relinked, keeping their relative order.
+    ///
+    /// Panics if `key` returns an index greater or equal to `n`.
+    pub fn distribute<F>(mut self, n: usize, mut key: F) -> Vec<List<T, A>>
+    where
+        F: FnMut(&T) -> usize,
+        A: Clone,
+    {
+        let mut lists = (0..n)
+            .map(|_| List::new_in(self.alloc.clone()))
+            .collect::<Vec<_>>();
+        while let Some(v) = self.front() {
+            let i = key(v);
+            assert!(i < n, "Cannot distribute to a nonexistent list");
+            lists[i].push_back_node(self.pop_front_node().unwrap());
+        }
+        lists
+    }
+
+    /// Cut the list into runs of consecutive elements for which
+    /// `same_group(a, b)` returns `true` in O(n).
+    ///
+    /// `same_group` is given every pair of adjacent elements `a` and `b`.
+    pub fn chunk_by<F>(mut self, mut same_group: F) -> List<List<T, A>>
+    where
+        F: FnMut(&T, &T) -> bool,
+        A: Clone,
+    {
+        let mut r = List::new();
+        while !self.is_empty() {
+            let chunk_len = 1 + self
+                .iter()
+                .zip(self.iter().skip(1))
+                .take_while(|&(a, b)| same_group(a, b))
+                .count();
+            let rest = self.cursor().split(chunk_len);
+            r.push_back(mem::replace(&mut self, rest));
         }
+        r
+    }
+
+    /// Cut the list into chunks of `chunk_len` elements in O(n).
+    ///
+    /// The last chunk is shorter if `chunk_len` does not divide the length
+    /// of the list.
+    ///
+    /// Panics if `chunk_len` is 0.
+    pub fn chunks(mut self, chunk_len: usize) -> List<List<T, A>>
+    where
+        A: Clone,
+    {
+        assert!(chunk_len != 0, "Cannot cut chunks of 0 elements");
+        let mut r = List::new();
+        while !self.is_empty() {
+            let rest = self.cursor().split(chunk_len);
+            r.push_back(mem::replace(&mut self, rest));
+        }
+        r
+    }
+
+    /// Retain only the elements for which `f` returns `true` in O(n).
+    ///
+    /// If `f` panics, the list keeps all the elements not yet visited.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|v| f(v));
+    }
+
+    /// Retain only the elements for which `f` returns `true` in O(n), with
+    /// mutable access to the elements.
+    ///
+    /// If `f` panics, the list keeps all the elements not yet visited.
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let len = self.len;
+        self.cursor().retain_next(len, f);
+    }
 
-        let tail_link;
-        let mut head_link = &mut self.head;
-        let mut i = 0;
-        loop {
-            if i == at {
-                tail_link = head_link.take();
-                break;
+    /// Remove consecutive repeated elements in O(n).
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Remove consecutive elements that resolve to the same key in O(n).
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Remove consecutive elements satisfying `same_bucket` in O(n).
+    ///
+    /// `same_bucket(a, b)` is given the element `a` following the retained
+    /// element `b`, and `a` is removed if it returns `true`.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let mut iter = self.iter_mut();
+        let mut retained = match iter.next() {
+            Some(v) => v,
+            None => return,
+        };
+        while let Some(v) = iter.peek_next_mut() {
+            if same_bucket(v, retained) {
+                iter.remove_next();
+            } else {
+                retained = iter.next().unwrap();
             }
-            if let Some(ref mut node) = *{ head_link } {
-                head_link = &mut node.next;
-                i += 1;
+        }
+    }
+
+    /// Same as `dedup_by()` but returns the removed elements, in order, in O(n).
+    pub fn remove_dups_by<F>(&mut self, mut same_bucket: F) -> List<T, A>
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+        A: Clone,
+    {
+        let mut removed = List::new_in(self.alloc.clone());
+        let mut iter = self.iter_mut();
+        let mut retained = match iter.next() {
+            Some(v) => v,
+            None => return removed,
+        };
+        while let Some(v) = iter.peek_next_mut() {
+            if same_bucket(v, retained) {
+                removed.push_back_node(iter.remove_next_node().unwrap());
             } else {
-                unreachable!();
+                retained = iter.next().unwrap();
             }
         }
-        List {
-            len: mem::replace(&mut self.len, at) - at,
-            head: tail_link,
+        removed
+    }
+}
+
+impl<T, A: Allocator> List<List<T, A>> {
+    /// Link all the lists together in O(self.len).
+    ///
+    /// The result is the first list, with the others appended to it, or a new
+    /// list using `A::default()` if there is none.
+    pub fn concat(self) -> List<T, A>
+    where
+        A: Default,
+    {
+        let mut lists = self.into_iter();
+        let mut r = lists.next().unwrap_or_else(|| List::new_in(A::default()));
+        for mut l in lists {
+            r.append(&mut l);
         }
+        r
     }
 }
 
@@ -55,6 +272,19 @@ fn append() {
     a.append(&mut b);
     assert_eq!(a.len(), 5);
     assert_eq!(b.len(), 0);
+    a.check_links();
+    b.check_links();
+
+    let mut c = (5..8).collect::<List<_>>();
+    a.append(&mut c);
+    a.check_links();
+    c.check_links();
+    assert_eq!(a.back(), Some(&7));
+    a.append(&mut c);
+    a.check_links();
+    a.push_back(8);
+    a.check_links();
+    assert_eq!(a.len(), 9);
 }
 
 #[test]
@@ -70,6 +300,8 @@ fn split_off() {
     assert_eq!(*a.back().unwrap(), 13);
     assert_eq!(*b.front().unwrap(), 12);
     assert_eq!(*b.back().unwrap(), 0);
+    a.check_links();
+    b.check_links();
 
     let mut a = List::new();
     for i in 0..10 {
@@ -78,6 +310,14 @@ fn split_off() {
     let b = a.split_off(10);
     assert_eq!(a.len(), 10);
     assert_eq!(b.len(), 0);
+    a.check_links();
+    b.check_links();
+
+    let b = a.split_off(0);
+    assert_eq!(a.len(), 0);
+    assert_eq!(b.len(), 10);
+    a.check_links();
+    b.check_links();
 }
 
 #[test]
@@ -89,3 +329,201 @@ fn split_off_panic() {
     }
     let _ = a.split_off(11);
 }
+
+#[test]
+fn reverse() {
+    let mut a = (0..10).collect::<List<_>>();
+    a.reverse();
+    a.check_links();
+    assert_eq!(a, (0..10).rev().collect());
+    a.push_back(-1);
+    assert_eq!(a.back(), Some(&-1));
+
+    let mut a = List::<i32>::new();
+    a.reverse();
+    a.check_links();
+    a.push_back(0);
+    a.reverse();
+    a.check_links();
+    assert_eq!(a.front(), Some(&0));
+}
+
+#[test]
+fn retain() {
+    let mut a = (0..20).collect::<List<_>>();
+    a.retain(|v| v % 3 == 0);
+    a.check_links();
+    assert_eq!(a, [0, 3, 6, 9, 12, 15, 18].iter().cloned().collect());
+    a.retain_mut(|v| {
+        *v += 1;
+        *v < 18
+    });
+    a.check_links();
+    assert_eq!(a, [1, 4, 7, 10, 13, 16].iter().cloned().collect());
+    a.retain(|_| false);
+    a.check_links();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn retain_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut a = (0..10).collect::<List<_>>();
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        a.retain(|&v| {
+            assert!(v < 6);
+            v % 2 == 1
+        })
+    }));
+    assert!(r.is_err());
+    a.check_links();
+    assert_eq!(a, [1, 3, 5, 6, 7, 8, 9].iter().cloned().collect());
+}
+
+#[test]
+fn dedup() {
+    let mut a = [1, 1, 2, 3, 3, 3, 1, 4, 4]
+        .iter()
+        .cloned()
+        .collect::<List<_>>();
+    a.dedup();
+    a.check_links();
+    assert_eq!(a, [1, 2, 3, 1, 4].iter().cloned().collect());
+
+    let mut a = (0..10).collect::<List<_>>();
+    a.dedup_by_key(|v| *v / 3);
+    a.check_links();
+    assert_eq!(a, [0, 3, 6, 9].iter().cloned().collect());
+
+    let mut a = List::with_node_pool(crate::NodePool::new(8));
+    a.extend([1, 1, 2, 2, 2].iter().cloned());
+    a.dedup();
+    a.check_links();
+    assert_eq!(a, [1, 2].iter().cloned().collect());
+    assert_eq!(a.node_pool().unwrap().len(), 3);
+
+    let mut a = [(0, 'a'), (0, 'b'), (1, 'c'), (1, 'd'), (1, 'e')]
+        .iter()
+        .cloned()
+        .collect::<List<_>>();
+    let removed = a.remove_dups_by(|a, b| a.0 == b.0);
+    a.check_links();
+    removed.check_links();
+    assert_eq!(a, [(0, 'a'), (1, 'c')].iter().cloned().collect());
+    assert_eq!(
+        removed,
+        [(0, 'b'), (1, 'd'), (1, 'e')].iter().cloned().collect()
+    );
+
+    let mut a = List::<i32>::new();
+    assert!(a.remove_dups_by(|_, _| true).is_empty());
+}
+
+#[test]
+fn rotate() {
+    let mut a = (0..7).collect::<List<_>>();
+    a.rotate_left(3);
+    a.check_links();
+    assert_eq!(a, [3, 4, 5, 6, 0, 1, 2].iter().cloned().collect());
+    a.rotate_right(3);
+    a.check_links();
+    assert_eq!(a, (0..7).collect());
+    a.rotate_left(7 * 3 + 1);
+    a.check_links();
+    assert_eq!(a, [1, 2, 3, 4, 5, 6, 0].iter().cloned().collect());
+    a.rotate_right(15);
+    a.check_links();
+    assert_eq!(a, (0..7).collect());
+    a.rotate_right(0);
+    a.rotate_left(7);
+    assert_eq!(a, (0..7).collect());
+
+    let mut a = List::<i32>::new();
+    a.rotate_left(3);
+    a.rotate_right(3);
+    a.check_links();
+    a.push_back(1);
+    a.rotate_left(3);
+    a.rotate_right(3);
+    a.check_links();
+    assert_eq!(a.front(), Some(&1));
+}
+
+#[test]
+fn rotate_pooled() {
+    let mut a = List::with_node_pool(crate::NodePool::new(8));
+    a.extend(0..5);
+    a.rotate_left(2);
+    a.check_links();
+    assert_eq!(a, [2, 3, 4, 0, 1].iter().cloned().collect());
+    a.rotate_right(1);
+    a.check_links();
+    assert_eq!(a, [1, 2, 3, 4, 0].iter().cloned().collect());
+    a.pop_front();
+    assert_eq!(a.node_pool().map(|pool| pool.len()), Some(1));
+}
+
+#[test]
+fn partition() {
+    let (a, b) = (0..10).collect::<List<_>>().partition(|v| v % 3 == 0);
+    a.check_links();
+    b.check_links();
+    assert_eq!(a, [0, 3, 6, 9].iter().cloned().collect());
+    assert_eq!(b, [1, 2, 4, 5, 7, 8].iter().cloned().collect());
+
+    let (a, b) = List::<i32>::new().partition(|_| true);
+    assert!(a.is_empty() && b.is_empty());
+
+    let l = (0..10).collect::<List<_>>().distribute(4, |v| v % 4);
+    assert_eq!(l.len(), 4);
+    for (i, l) in l.iter().enumerate() {
+        l.check_links();
+        assert_eq!(*l, (i..10).step_by(4).collect());
+    }
+}
+
+#[test]
+#[should_panic]
+fn distribute_panic() {
+    let _ = (0..10).collect::<List<_>>().distribute(2, |v| *v);
+}
+
+#[test]
+fn chunks() {
+    let l = [1, 1, 2, 3, 3, 3, 1].iter().cloned().collect::<List<_>>();
+    let chunks = l.chunk_by(|a, b| a == b);
+    assert_eq!(chunks.len(), 4);
+    for chunk in &chunks {
+        chunk.check_links();
+    }
+    assert_eq!(
+        chunks.iter().map(|c| c.len()).collect::<Vec<_>>(),
+        [2, 1, 3, 1]
+    );
+    let l = chunks.concat();
+    l.check_links();
+    assert_eq!(l, [1, 1, 2, 3, 3, 3, 1].iter().cloned().collect());
+
+    let chunks = (0..10).collect::<List<_>>().chunks(4);
+    assert_eq!(chunks.len(), 3);
+    for (i, chunk) in chunks.iter().enumerate() {
+        chunk.check_links();
+        assert_eq!(*chunk, (i * 4..10.min(i * 4 + 4)).collect());
+    }
+    let l = chunks.concat();
+    l.check_links();
+    assert_eq!(l, (0..10).collect());
+
+    assert!(List::<i32>::new().chunks(3).is_empty());
+    assert!(List::<i32>::new().chunk_by(|_, _| true).is_empty());
+    assert!(List::<List<i32>>::new().concat().is_empty());
+
+    let alloc = crate::ops::TestAlloc::default();
+    let mut l = List::new_in(alloc.clone());
+    l.extend(0..5);
+    let l = l.chunks(2).concat();
+    l.check_links();
+    assert!(l.iter().eq(&[0, 1, 2, 3, 4]));
+    assert_eq!(alloc.live(), 5);
+}
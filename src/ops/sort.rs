@@ -0,0 +1,524 @@
+use crate::{Allocator, Link, List};
+use alloc::vec::Vec;
+use core::cmp::Ordering::{self, Less};
+use core::mem;
+use core::ptr;
+
+/// Sorting by relinking the nodes in place - **Unstable API**.
+///
+/// All the sorts are stable and never move nor reallocate the values.
+impl<T, A: Allocator> List<T, A> {
+    /// Sort the list in O(n * log(n)) with O(1) extra memory.
+    ///
+    /// This is a natural merge sort: already ascending runs are kept as is, so
+    /// a nearly sorted list is sorted in close to O(n).
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.merge_sort(|a, b| a.lt(b));
+    }
+
+    /// Sort the list with the comparator function `compare`.
+    ///
+    /// See `sort()`.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.merge_sort(|a, b| compare(a, b) == Less);
+    }
+
+    /// Sort the list with the key extraction function `f`.
+    ///
+    /// The key is computed at every comparison, see `sort_by_cached_key()`
+    /// when `f` is expensive.
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.merge_sort(|a, b| f(a).lt(&f(b)));
+    }
+
+    /// Sort the list with the key extraction function `f`, calling `f` only
+    /// once per element.
+    ///
+    /// Unlike the other sorts, it takes O(n) extra memory to cache the keys.
+    pub fn sort_by_cached_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        if self.len < 2 {
+            return;
+        }
+        let mut keys: Vec<_> = self.iter().map(f).zip(0..self.len).collect();
+        keys.sort_unstable();
+
+        // Nothing below calls into user code, the list cannot be left half
+        // relinked.
+        let mut nodes = Vec::with_capacity(self.len);
+        let mut head_link = self.head.take();
+        while let Some(mut node) = head_link {
+            head_link = node.next.take();
+            nodes.push(Some(node));
+        }
+        for &(_, i) in keys.iter().rev() {
+            let mut node = nodes[i].take().unwrap();
+            node.next = self.head.take();
+            self.head = Some(node);
+        }
+        self.recompute_tail();
+    }
+
+    /// Merge the sorted list `other` into this sorted list in
+    /// O(self.len + other.len), leaving `other` empty.
+    ///
+    /// The merge is stable, elements from `self` come first on ties.
+    pub fn merge(&mut self, other: &mut List<T, A>)
+    where
+        T: Ord,
+    {
+        self.merge_by(other, |a, b| a.cmp(b));
+    }
+
+    /// Merge the list `other`, sorted with the comparator function `compare`,
+    /// into this list.
+    ///
+    /// See `merge()`.
+    pub fn merge_by<F>(&mut self, other: &mut List<T, A>, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.is_empty() || other.is_empty() {
+            self.append(other);
+            return;
+        }
+        let (a_len, b_len) = (self.len, other.len);
+        let (a_tail, b_tail) = (self.tail, other.tail);
+        let a_end = unsafe { ptr::addr_of_mut!((*a_tail).next) };
+        let b_end = unsafe { ptr::addr_of_mut!((*b_tail).next) };
+        self.append(other);
+
+        let guard = TailGuard(self);
+        let head: *mut Link<T, A> = &mut guard.0.head;
+        let end = unsafe {
+            merge_runs(head, a_len, a_end, b_len, b_end, &mut |a: &T, b: &T| {
+                compare(a, b) == Less
+            })
+        };
+        // No need to walk the list again, the merged list ends with either run.
+        mem::forget(guard);
+        self.tail = if end == b_end { b_tail } else { a_tail };
+    }
+
+    /// Sort the list with the integer key extraction function `key` in
+    /// O(n * b), with `b` the number of bytes needed to represent the greatest
+    /// key.
+    ///
+    /// This is a stable LSD radix sort, distributing the nodes into 256
+    /// buckets per byte of the key. `key` is called `b + 1` times per element.
+    pub fn radix_sort_by_key<F>(&mut self, mut key: F)
+    where
+        F: FnMut(&T) -> u64,
+        A: Clone,
+    {
+        let max_key = self.iter().map(&mut key).max().unwrap_or(0);
+        let mut buckets = Buckets {
+            buckets: (0..256).map(|_| List::new_in(self.alloc.clone())).collect(),
+            list: self,
+        };
+        let mut shift = 0;
+        while shift < 64 && max_key >> shift != 0 {
+            while let Some(v) = buckets.list.front() {
+                let bucket = (key(v) >> shift) as u8 as usize;
+                let node = buckets.list.pop_front_node().unwrap();
+                buckets.buckets[bucket].push_back_node(node);
+            }
+            buckets.concat();
+            shift += 8;
+        }
+    }
+
+    fn merge_sort<F>(&mut self, mut is_less: F)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        if self.len < 2 {
+            return;
+        }
+        // Every node stays reachable from the head while `is_less` runs, so
+        // fixing the tail is all it takes to recover from a panic.
+        let guard = TailGuard(self);
+        let head: *mut Link<T, A> = &mut guard.0.head;
+        unsafe {
+            loop {
+                let mut runs = 0;
+                let mut next_link = head;
+                while (*next_link).is_some() {
+                    let (a_end, a_len) = ascending_run(next_link, &mut is_less);
+                    runs += 1;
+                    if (*a_end).is_none() {
+                        break;
+                    }
+                    let (b_end, b_len) = ascending_run(a_end, &mut is_less);
+                    next_link = merge_runs(next_link, a_len, a_end, b_len, b_end, &mut is_less);
+                }
+                if runs == 1 {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+struct TailGuard<'a, T, A: Allocator>(&'a mut List<T, A>);
+
+impl<'a, T, A: Allocator> Drop for TailGuard<'a, T, A> {
+    fn drop(&mut self) {
+        self.0.recompute_tail();
+    }
+}
+
+/// Radix sort buckets, going back to the list even on panic.
+struct Buckets<'a, T, A: Allocator> {
+    list: &'a mut List<T, A>,
+    buckets: Vec<List<T, A>>,
+}
+
+impl<'a, T, A: Allocator> Buckets<'a, T, A> {
+    fn concat(&mut self) {
+        for bucket in &mut self.buckets {
+            self.list.append(bucket);
+        }
+    }
+}
+
+impl<'a, T, A: Allocator> Drop for Buckets<'a, T, A> {
+    fn drop(&mut self) {
+        self.concat();
+    }
+}
+
+/// Find the end of the ascending run starting at `*start`, which must not be
+/// empty.
+///
+/// Returns the link following the run and the length of the run.
+unsafe fn ascending_run<T, A, F>(
+    start: *mut Link<T, A>,
+    is_less: &mut F,
+) -> (*mut Link<T, A>, usize)
+where
+    A: Allocator,
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut node = (*start).as_ref().unwrap().as_ptr();
+    let mut len = 1;
+    while let Some(ref next) = (*node).next {
+        if is_less(&next.value, &(*node).value) {
+            break;
+        }
+        node = next.as_ptr();
+        len += 1;
+    }
+    (ptr::addr_of_mut!((*node).next), len)
+}
+
+/// The link following the node at `*link`, which must not be empty.
+///
+/// Links are always derived from raw node pointers, never from a reference to
+/// the node, so they all stay valid while the nodes are relinked.
+unsafe fn link_after<T, A: Allocator>(link: *mut Link<T, A>) -> *mut Link<T, A> {
+    let node = (*link).as_ref().unwrap().as_ptr();
+    ptr::addr_of_mut!((*node).next)
+}
+
+/// Merge the run `a` starting at `*a_start` with the run `b` directly
+/// following it at `*a_end`, and ending at `*b_end`.
+///
+/// Nodes from `b` are unlinked and relinked before nodes from `a` one at a
+/// time, so the list is whole every time `is_less` is called.
+///
+/// Returns the link following the merged run.
+unsafe fn merge_runs<T, A, F>(
+    a_start: *mut Link<T, A>,
+    mut a_len: usize,
+    a_end: *mut Link<T, A>,
+    mut b_len: usize,
+    b_end: *mut Link<T, A>,
+    is_less: &mut F,
+) -> *mut Link<T, A>
+where
+    A: Allocator,
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut next_link = a_start;
+    while a_len > 0 && b_len > 0 {
+        let b_first = {
+            let a = &(*next_link).as_ref().unwrap().value;
+            let b = &(*a_end).as_ref().unwrap().value;
+            is_less(b, a)
+        };
+        if b_first {
+            let mut node = (*a_end).take().unwrap();
+            *a_end = node.next.take();
+            node.next = (*next_link).take();
+            *next_link = Some(node);
+            b_len -= 1;
+        } else {
+            a_len -= 1;
+        }
+        next_link = link_after(next_link);
+    }
+    // Whatever is left of `a` is already in place before `a_end`, and whatever
+    // is left of `b` right after it, up to `b_end`.
+    if b_len > 0 {
+        b_end
+    } else {
+        a_end
+    }
+}
+
+#[test]
+fn sort() {
+    let mut l: List<i32> = List::new();
+    l.sort();
+    assert!(l.is_empty());
+
+    let mut l = (0..10)
+        .rev()
+        .chain(20..30)
+        .chain(10..20)
+        .collect::<List<_>>();
+    l.sort();
+    l.check_links();
+    assert_eq!(l, (0..30).collect());
+
+    let mut l = (0..100).map(|i| (i * 7919) % 101).collect::<List<_>>();
+    l.sort_by(|a, b| b.cmp(a));
+    l.check_links();
+    assert_eq!(l.len(), 100);
+    assert!(l.iter().zip(l.iter().skip(1)).all(|(a, b)| a > b));
+}
+
+#[test]
+fn sort_stable() {
+    let input = || (0..50).map(|i| ((i * 13) % 7, i));
+    let mut expected = input().collect::<Vec<_>>();
+    expected.sort_by_key(|&(k, _)| k);
+
+    let mut l = input().collect::<List<_>>();
+    l.sort_by_key(|&(k, _)| k);
+    l.check_links();
+    assert_eq!(l, expected.iter().cloned().collect());
+
+    let mut l = input().collect::<List<_>>();
+    let mut calls = 0;
+    l.sort_by_cached_key(|&(k, _)| {
+        calls += 1;
+        k
+    });
+    l.check_links();
+    assert_eq!(calls, 50);
+    assert_eq!(l, expected.iter().cloned().collect());
+}
+
+#[test]
+fn sort_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut l = (0..20).rev().collect::<List<_>>();
+    let mut calls = 0;
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        l.sort_by(|a, b| {
+            calls += 1;
+            if calls == 15 {
+                panic!("boom");
+            }
+            a.cmp(b)
+        })
+    }));
+    assert!(r.is_err());
+    l.check_links();
+    assert_eq!(l.len(), 20);
+    l.sort();
+    assert_eq!(l, (0..20).collect());
+}
+
+#[test]
+fn radix_sort() {
+    let input = || (0..300u64).map(|i| ((i * 7919) % 1021 * 65599, i));
+    let mut expected = input().collect::<Vec<_>>();
+    expected.sort_by_key(|&(k, _)| k);
+
+    let mut l = input().collect::<List<_>>();
+    l.radix_sort_by_key(|&(k, _)| k);
+    l.check_links();
+    assert_eq!(l, expected.iter().cloned().collect());
+
+    let mut l = (0..10).rev().map(|i| (i % 2, i)).collect::<List<_>>();
+    l.radix_sort_by_key(|&(k, _)| k);
+    assert_eq!(
+        l.iter().map(|&(_, i)| i).collect::<Vec<_>>(),
+        [8, 6, 4, 2, 0, 9, 7, 5, 3, 1]
+    );
+
+    let mut l = [u64::MAX, 0, 1 << 63].iter().cloned().collect::<List<_>>();
+    l.radix_sort_by_key(|&v| v);
+    l.check_links();
+    assert_eq!(l, [0, 1 << 63, u64::MAX].iter().cloned().collect());
+}
+
+#[test]
+fn radix_sort_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut l = (0..1000u64).rev().collect::<List<_>>();
+    let mut calls = 0;
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        l.radix_sort_by_key(|&v| {
+            calls += 1;
+            assert!(calls < 1500);
+            v
+        })
+    }));
+    assert!(r.is_err());
+    l.check_links();
+    assert_eq!(l.len(), 1000);
+    l.radix_sort_by_key(|&v| v);
+    assert_eq!(l, (0..1000).collect());
+}
+
+#[test]
+fn merge() {
+    let mut a = [1, 3, 3, 5, 7]
+        .iter()
+        .map(|&v| (v, 'a'))
+        .collect::<List<_>>();
+    let mut b = [0, 3, 4, 8, 9]
+        .iter()
+        .map(|&v| (v, 'b'))
+        .collect::<List<_>>();
+    a.merge_by(&mut b, |x, y| x.0.cmp(&y.0));
+    a.check_links();
+    b.check_links();
+    assert!(b.is_empty());
+    assert_eq!(
+        a.iter().cloned().collect::<Vec<_>>(),
+        [
+            (0, 'b'),
+            (1, 'a'),
+            (3, 'a'),
+            (3, 'a'),
+            (3, 'b'),
+            (4, 'b'),
+            (5, 'a'),
+            (7, 'a'),
+            (8, 'b'),
+            (9, 'b')
+        ]
+    );
+
+    let mut a = [2, 9].iter().cloned().collect::<List<_>>();
+    a.merge(&mut (0..4).collect());
+    a.check_links();
+    assert_eq!(a.back(), Some(&9));
+    a.merge(&mut (8..11).collect());
+    a.check_links();
+    assert_eq!(a.back(), Some(&10));
+
+    let mut a = (0..5).collect::<List<_>>();
+    a.merge(&mut List::new());
+    a.check_links();
+    let mut b = List::new();
+    b.merge(&mut a);
+    b.check_links();
+    assert!(a.is_empty());
+    assert_eq!(b, (0..5).collect());
+    b.merge(&mut (5..10).collect());
+    b.check_links();
+    assert_eq!(b, (0..10).collect());
+}
+
+#[cfg(feature = "bench")]
+mod benchs {
+    extern crate test;
+    use crate::List;
+    use test::{black_box, Bencher};
+
+    static LIST_SIZE: u64 = 64 * 1024;
+
+    fn make_list() -> List<u64> {
+        (0..LIST_SIZE)
+            .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 40)
+            .collect()
+    }
+
+    // The cursor based merge-sort from the crate documentation.
+    fn cursor_merge_sort(mut l: List<u64>) -> List<u64> {
+        fn merge(mut a: List<u64>, mut b: List<u64>) -> List<u64> {
+            let mut r = List::new();
+            {
+                let mut a = a.cursor();
+                let mut b = b.cursor();
+                let mut o = r.cursor();
+                loop {
+                    let a_first = match (a.value(), b.value()) {
+                        (Some(a), Some(b)) => a < b,
+                        _ => break,
+                    };
+                    if a_first {
+                        o.splice(&mut a.remove_n(1));
+                    } else {
+                        o.splice(&mut b.remove_n(1));
+                    }
+                }
+                o.splice(&mut a.truncate());
+                o.splice(&mut b.truncate());
+            }
+            r
+        }
+
+        let mut run_len = 1;
+        while run_len < l.len() {
+            let mut tail = l;
+            l = List::new();
+            let mut cl = l.cursor();
+            while !tail.is_empty() {
+                let mut a = tail;
+                let mut b = a.cursor().split(run_len);
+                tail = b.cursor().split(run_len);
+                cl.splice(&mut merge(a, b));
+            }
+            run_len *= 2;
+        }
+        l
+    }
+
+    #[bench]
+    fn sort_cursor_merge_sort(b: &mut Bencher) {
+        let l = make_list();
+        b.iter(|| black_box(cursor_merge_sort(l.clone())));
+    }
+
+    #[bench]
+    fn sort_merge_sort(b: &mut Bencher) {
+        let l = make_list();
+        b.iter(|| {
+            let mut l = l.clone();
+            l.sort();
+            black_box(l)
+        });
+    }
+
+    #[bench]
+    fn sort_radix_sort(b: &mut Bencher) {
+        let l = make_list();
+        b.iter(|| {
+            let mut l = l.clone();
+            l.radix_sort_by_key(|&v| v);
+            black_box(l)
+        });
+    }
+}
@@ -1,11 +1,13 @@
-use crate::List;
-use std::fmt;
-use std::hash::{Hash, Hasher};
-use std::iter::FromIterator;
+use crate::{Allocator, List};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
 
 /// Drop the list in O(n).
-impl<T> Drop for List<T> {
+impl<T, A: Allocator> Drop for List<T, A> {
     fn drop(&mut self) {
+        // No point filling up a pool that is about to be freed.
+        self.pool = None;
         self.clear();
     }
 }
@@ -18,7 +20,7 @@ impl<T> Default for List<T> {
 }
 
 /// A debug formatter.
-impl<T: fmt::Debug> fmt::Debug for List<T> {
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for List<T, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.iter()
             .fold(f.debug_list(), |mut b, e| {
@@ -32,26 +34,23 @@ impl<T: fmt::Debug> fmt::Debug for List<T> {
 /// Clone a list in O(n).
 ///
 /// `clone_from()` will reuse as many nodes from `self` as possible to avoid
-/// reallocation.
-impl<T: Clone> Clone for List<T> {
-    fn clone(&self) -> List<T> {
-        self.iter().cloned().collect()
+/// reallocation, and draws any missing node from the node pool of `self`.
+impl<T: Clone, A: Allocator + Clone> Clone for List<T, A> {
+    fn clone(&self) -> List<T, A> {
+        let mut r = List::new_in(self.alloc.clone());
+        r.extend(self.iter().cloned());
+        r
     }
 
     fn clone_from(&mut self, source: &Self) {
-        let mut src_iter = source.iter().cloned();
         let mut dst_iter = self.iter_mut();
-        while let Some(dst_v) = dst_iter.next() {
-            if let Some(v) = src_iter.next() {
-                *dst_v = v;
-            } else {
-                dst_iter.truncate_next();
-                return;
+        for v in source.iter().cloned() {
+            match dst_iter.next() {
+                Some(dst_v) => *dst_v = v,
+                None => dst_iter.insert_next(v),
             }
         }
-        for src_v in src_iter {
-            dst_iter.insert_next(src_v);
-        }
+        while dst_iter.remove_next().is_some() {}
     }
 }
 
@@ -64,21 +63,19 @@ impl<T> FromIterator<T> for List<T> {
     }
 }
 
-/// Extend the list from the content of `iter` in O(n).
-impl<T> Extend<T> for List<T> {
+/// Extend the list from the content of `iter` in O(iter.len()).
+impl<T, A: Allocator + Clone> Extend<T> for List<T, A> {
     fn extend<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = T>,
     {
-        let mut tail = self.iter_mut();
-        for _ in &mut tail {}
         for v in iter {
-            tail.insert_next(v)
+            self.push_back(v)
         }
     }
 }
 
-impl<T: Hash> Hash for List<T> {
+impl<T: Hash, A: Allocator> Hash for List<T, A> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.len().hash(state);
         for v in self {
@@ -0,0 +1,280 @@
+use crate::{Allocator, Cursor, CursorOrigin, Global, Link, List, ListIntoIter, Node};
+use core::ptr;
+
+/// A cursor owning its list.
+///
+/// Unlike `Cursor`, it does not borrow anything, so it can be stored next to
+/// other things in a struct. It moves like a `Cursor`, and `edit()` gives
+/// access to the whole `Cursor` API at its position.
+pub struct OwnedCursor<T, A: Allocator = Global> {
+    list: List<T, A>,
+    // The node before the cursor, null when the cursor is at the head.
+    prev_node: *mut Node<T, A>,
+    position: usize,
+}
+
+unsafe impl<T: Send, A: Allocator + Send> Send for OwnedCursor<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for OwnedCursor<T, A> {}
+
+impl<T, A: Allocator> List<T, A> {
+    /// Consume the list into a cursor at the beginning of the list (before the
+    /// first node).
+    pub fn into_cursor(self) -> OwnedCursor<T, A> {
+        OwnedCursor {
+            list: self,
+            prev_node: ptr::null_mut(),
+            position: 0,
+        }
+    }
+}
+
+impl<T, A: Allocator> OwnedCursor<T, A> {
+    fn next_link(&self) -> &Link<T, A> {
+        if self.prev_node.is_null() {
+            &self.list.head
+        } else {
+            unsafe { &(*self.prev_node).next }
+        }
+    }
+
+    /// Run `f` with a borrowing cursor at the same position, then move to
+    /// where `f` left it.
+    ///
+    /// This gives access to the whole `Cursor` API. Within `f`, the cursor
+    /// rewinds to the beginning of the list.
+    ///
+    /// ```
+    /// use fwdlist::List;
+    ///
+    /// let mut c = (0..5).collect::<List<_>>().into_cursor();
+    /// c.nth(2);
+    /// let head = c.edit(|c| {
+    ///     c.insert_iter(10..12);
+    ///     c.advance_while(|&v| v < 4);
+    ///     c.remove_n(10)
+    /// });
+    /// assert_eq!(c.position(), 6);
+    /// assert_eq!(head, (4..5).collect());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `f` swaps the cursor with a cursor over another list.
+    pub fn edit<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Cursor<'_, T, A>) -> R,
+    {
+        let head: *mut _ = &mut self.list.head;
+        let list_len: *const usize = &self.list.len;
+        let next_link = if self.prev_node.is_null() {
+            unsafe { &mut *head }
+        } else {
+            unsafe { &mut (*self.prev_node).next }
+        };
+        let mut c = Cursor {
+            next_link,
+            list_len: &mut self.list.len,
+            list_tail: &mut self.list.tail,
+            list_pool: &mut self.list.pool,
+            list_alloc: &self.list.alloc,
+            prev_node: self.prev_node,
+            position: self.position,
+            origin: CursorOrigin::head(head),
+        };
+        let r = f(&mut c);
+        assert!(
+            ptr::eq(&*c.list_len, list_len),
+            "the cursor was swapped with another list's cursor"
+        );
+        self.prev_node = c.prev_node;
+        self.position = c.position;
+        r
+    }
+
+    /// Returns the list, the cursor being dropped.
+    pub fn into_list(self) -> List<T, A> {
+        self.list
+    }
+
+    /// A read-only reference to the whole list.
+    pub fn list(&self) -> &List<T, A> {
+        &self.list
+    }
+
+    /// A read-only reference to the following node's value.
+    /// Return `None` if the cursor is past the end of the list.
+    pub fn value(&self) -> Option<&T> {
+        self.next_link().as_ref().map(|node| &node.value)
+    }
+
+    /// A mutable reference to the following node's value.
+    /// Return `None` if the cursor is past the end of the list.
+    pub fn value_mut(&mut self) -> Option<&mut T> {
+        let value = self.edit(|c| c.value_mut().map(|v| v as *mut T));
+        value.map(|v| unsafe { &mut *v })
+    }
+
+    /// Move the cursor past the following node. Returns `true` on success,
+    /// `false` if the cursor is already at the end of the list.
+    pub fn advance(&mut self) -> bool {
+        self.edit(|c| c.advance())
+    }
+
+    /// The lengths of the tail.
+    /// This is O(1).
+    pub fn len(&self) -> usize {
+        self.list.len - self.position
+    }
+
+    /// This is O(1).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The position from the beginning of the list.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Move the cursor back to the beginning of the list in O(1).
+    pub fn rewind(&mut self) {
+        self.prev_node = ptr::null_mut();
+        self.position = 0;
+    }
+
+    /// Move the cursor to `position` from the beginning of the list, in
+    /// O(position) when moving backward, and O(position - self.position)
+    /// otherwise.
+    /// Returns the new position, which could be less than `position` if there
+    /// is not enough nodes.
+    pub fn seek(&mut self, position: usize) -> usize {
+        self.edit(|c| c.seek(position))
+    }
+
+    /// Move forward by `nth` nodes in O(min(nth, self.len)).
+    /// Returns the number of nodes skipped, which could be less than `nth` if
+    /// there is not enough remaining nodes.
+    pub fn nth(&mut self, nth: usize) -> usize {
+        self.edit(|c| c.nth(nth))
+    }
+
+    /// Move forward before the last node of the list in O(self.len - 1).
+    /// Returns the number of nodes skipped.
+    pub fn last(&mut self) -> usize {
+        self.edit(|c| c.last())
+    }
+
+    /// Move the cursor forward after the end of the list in O(self.len).
+    pub fn end(&mut self) -> usize {
+        self.edit(|c| c.end())
+    }
+
+    /// Create a new node containing the value `v` and insert it at the current
+    /// location in O(1).
+    pub fn insert(&mut self, v: T) -> &mut T
+    where
+        A: Clone,
+    {
+        let value = self.edit(|c| c.insert(v) as *mut T);
+        unsafe { &mut *value }
+    }
+
+    /// Remove the following node and return the contained value in O(1).
+    /// Return `None` if the cursor is past the end of the list.
+    pub fn remove(&mut self) -> Option<T> {
+        self.edit(|c| c.remove())
+    }
+}
+
+/// A cursor at the beginning of the list.
+impl<T, A: Allocator> From<List<T, A>> for OwnedCursor<T, A> {
+    fn from(list: List<T, A>) -> OwnedCursor<T, A> {
+        list.into_cursor()
+    }
+}
+
+/// A cursor at the beginning of the elements not yet consumed by the
+/// iterator.
+impl<T, A: Allocator> From<ListIntoIter<T, A>> for OwnedCursor<T, A> {
+    fn from(iter: ListIntoIter<T, A>) -> OwnedCursor<T, A> {
+        iter.into_list().into_cursor()
+    }
+}
+
+/// An iterator over the whole list, regardless of the cursor position.
+impl<T, A: Allocator> From<OwnedCursor<T, A>> for ListIntoIter<T, A> {
+    fn from(c: OwnedCursor<T, A>) -> ListIntoIter<T, A> {
+        c.into_list().into_iter()
+    }
+}
+
+#[test]
+fn owned_cursor() {
+    struct Editor {
+        cursor: OwnedCursor<i32>,
+    }
+
+    let mut e = Editor {
+        cursor: (0..10).collect::<List<_>>().into_cursor(),
+    };
+    assert_eq!(e.cursor.nth(3), 3);
+    assert_eq!(e.cursor.value(), Some(&3));
+    *e.cursor.insert(42) += 1;
+    assert_eq!(e.cursor.position(), 4);
+    assert_eq!(e.cursor.remove(), Some(3));
+    assert_eq!(e.cursor.edit(|c| c.remove_n(2)), (4..6).collect());
+    *e.cursor.value_mut().unwrap() *= 10;
+
+    // Moving the cursor around must not invalidate it.
+    let mut moved = e;
+    assert_eq!(moved.cursor.value(), Some(&60));
+    assert_eq!(moved.cursor.len(), 4);
+    assert_eq!(moved.cursor.edit(|c| c.split(2)), (8..10).collect());
+    moved.cursor.end();
+    moved.cursor.edit(|c| c.splice(&mut (20..22).collect()));
+    assert_eq!(moved.cursor.position(), 8);
+    assert!(!moved.cursor.advance());
+    moved.cursor.insert(22);
+
+    assert_eq!(moved.cursor.seek(2), 2);
+    assert_eq!(moved.cursor.value(), Some(&2));
+    assert_eq!(moved.cursor.seek(4), 4);
+    assert_eq!(moved.cursor.value(), Some(&60));
+    assert_eq!(moved.cursor.seek(100), 9);
+    moved.cursor.rewind();
+    assert_eq!(moved.cursor.position(), 0);
+    assert_eq!(moved.cursor.len(), 9);
+    assert_eq!(moved.cursor.last(), 8);
+    assert_eq!(moved.cursor.edit(|c| c.truncate()), (22..23).collect());
+
+    let l = moved.cursor.into_list();
+    l.check_links();
+    assert_eq!(l, [0, 1, 2, 43, 60, 7, 20, 21].iter().cloned().collect());
+}
+
+#[test]
+fn owned_cursor_into_iter() {
+    let mut iter = (0..5).collect::<List<_>>().into_iter();
+    iter.next();
+    let mut c = OwnedCursor::from(iter);
+    assert_eq!(c.position(), 0);
+    assert_eq!(c.value(), Some(&1));
+    c.end();
+    c.insert(5);
+    let iter = ListIntoIter::from(c);
+    assert_eq!(iter.collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+}
+
+#[test]
+#[should_panic(expected = "swapped")]
+// The other list must be leaked to outlive the closure.
+#[cfg_attr(miri, ignore)]
+fn owned_cursor_edit_swap() {
+    let mut c = (0..5).collect::<List<_>>().into_cursor();
+    c.edit(|c| {
+        let other: &'static mut List<i32> = Box::leak(Box::new((0..3).collect()));
+        let mut other = other.cursor();
+        other.advance();
+        core::mem::swap(c, &mut other);
+    });
+}
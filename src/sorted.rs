@@ -0,0 +1,280 @@
+//! Set operations on sorted lists.
+//!
+//! Every operation here expects both lists to be sorted in ascending order,
+//! which is checked in O(n) when debug assertions are enabled. Elements are
+//! matched one to one, so duplicated elements behave as in a multiset: the
+//! intersection of `[1, 1, 2]` and `[1, 1, 1]` is `[1, 1]`.
+//!
+//! The consuming operations, like `List::into_union()`, move the nodes to the
+//! resulting list and drop the left overs. The borrowing ones, like
+//! `List::union()`, return lazy iterators over references.
+
+use crate::{Allocator, Global, List, ListIter};
+use core::cmp::Ordering::*;
+use core::iter::Peekable;
+
+/// Which elements a set operation keeps.
+#[derive(Clone, Copy)]
+struct SetOp {
+    only_a: bool,
+    only_b: bool,
+    both: bool,
+}
+
+const UNION: SetOp = SetOp {
+    only_a: true,
+    only_b: true,
+    both: true,
+};
+const INTERSECTION: SetOp = SetOp {
+    only_a: false,
+    only_b: false,
+    both: true,
+};
+const DIFFERENCE: SetOp = SetOp {
+    only_a: true,
+    only_b: false,
+    both: false,
+};
+const SYMMETRIC_DIFFERENCE: SetOp = SetOp {
+    only_a: true,
+    only_b: true,
+    both: false,
+};
+
+fn debug_assert_sorted<T: Ord, A: Allocator>(l: &List<T, A>) {
+    if cfg!(debug_assertions) {
+        let mut iter = l.iter();
+        if let Some(mut prev) = iter.next() {
+            for v in iter {
+                assert!(prev <= v, "list is not sorted");
+                prev = v;
+            }
+        }
+    }
+}
+
+fn set_op<T, A>(mut a: List<T, A>, mut b: List<T, A>, op: SetOp) -> List<T, A>
+where
+    T: Ord,
+    A: Allocator + Clone,
+{
+    debug_assert_sorted(&a);
+    debug_assert_sorted(&b);
+    let mut r = List::new_in(a.alloc.clone());
+    while let (Some(x), Some(y)) = (a.front(), b.front()) {
+        let (node, keep) = match x.cmp(y) {
+            Less => (a.pop_front_node(), op.only_a),
+            Greater => (b.pop_front_node(), op.only_b),
+            Equal => {
+                b.pop_front_node();
+                (a.pop_front_node(), op.both)
+            }
+        };
+        if keep {
+            r.push_back_node(node.unwrap());
+        }
+    }
+    if op.only_a {
+        r.append(&mut a);
+    }
+    if op.only_b {
+        r.append(&mut b);
+    }
+    r
+}
+
+/// Set operations on sorted lists, see the `sorted` module.
+impl<T: Ord, A: Allocator> List<T, A> {
+    /// Consume both lists and returns the elements in `self` or `other` in
+    /// O(self.len + other.len).
+    ///
+    /// Elements present in both lists are taken from `self`.
+    pub fn into_union(self, other: List<T, A>) -> List<T, A>
+    where
+        A: Clone,
+    {
+        set_op(self, other, UNION)
+    }
+
+    /// Consume both lists and returns the elements in `self` and `other` in
+    /// O(self.len + other.len).
+    ///
+    /// Elements are taken from `self`.
+    pub fn into_intersection(self, other: List<T, A>) -> List<T, A>
+    where
+        A: Clone,
+    {
+        set_op(self, other, INTERSECTION)
+    }
+
+    /// Consume both lists and returns the elements in `self` but not in
+    /// `other` in O(self.len + other.len).
+    pub fn into_difference(self, other: List<T, A>) -> List<T, A>
+    where
+        A: Clone,
+    {
+        set_op(self, other, DIFFERENCE)
+    }
+
+    /// Consume both lists and returns the elements in `self` or `other` but
+    /// not in both in O(self.len + other.len).
+    pub fn into_symmetric_difference(self, other: List<T, A>) -> List<T, A>
+    where
+        A: Clone,
+    {
+        set_op(self, other, SYMMETRIC_DIFFERENCE)
+    }
+
+    /// Returns an iterator over the elements in `self` or `other`.
+    ///
+    /// Elements present in both lists are yielded from `self`.
+    pub fn union<'a>(&'a self, other: &'a List<T, A>) -> Union<'a, T, A> {
+        Union(SetOpIter::new(self, other))
+    }
+
+    /// Returns an iterator over the elements in `self` and `other`.
+    ///
+    /// Elements are yielded from `self`.
+    pub fn intersection<'a>(&'a self, other: &'a List<T, A>) -> Intersection<'a, T, A> {
+        Intersection(SetOpIter::new(self, other))
+    }
+
+    /// Returns an iterator over the elements in `self` but not in `other`.
+    pub fn difference<'a>(&'a self, other: &'a List<T, A>) -> Difference<'a, T, A> {
+        Difference(SetOpIter::new(self, other))
+    }
+
+    /// Returns an iterator over the elements in `self` or `other` but not in
+    /// both.
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a List<T, A>,
+    ) -> SymmetricDifference<'a, T, A> {
+        SymmetricDifference(SetOpIter::new(self, other))
+    }
+}
+
+struct SetOpIter<'a, T, A: Allocator> {
+    a: Peekable<ListIter<'a, T, A>>,
+    b: Peekable<ListIter<'a, T, A>>,
+}
+
+impl<'a, T: Ord, A: Allocator> SetOpIter<'a, T, A> {
+    fn new(a: &'a List<T, A>, b: &'a List<T, A>) -> SetOpIter<'a, T, A> {
+        debug_assert_sorted(a);
+        debug_assert_sorted(b);
+        SetOpIter {
+            a: a.iter().peekable(),
+            b: b.iter().peekable(),
+        }
+    }
+
+    fn next(&mut self, op: SetOp) -> Option<&'a T> {
+        loop {
+            let order = match (self.a.peek(), self.b.peek()) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) if op.only_a => Less,
+                (None, Some(_)) if op.only_b => Greater,
+                _ => return None,
+            };
+            let (v, keep) = match order {
+                Less => (self.a.next(), op.only_a),
+                Greater => (self.b.next(), op.only_b),
+                Equal => {
+                    self.b.next();
+                    (self.a.next(), op.both)
+                }
+            };
+            if keep {
+                return v;
+            }
+        }
+    }
+}
+
+/// Iterator over the union of two sorted lists.
+pub struct Union<'a, T, A: Allocator = Global>(SetOpIter<'a, T, A>);
+
+impl<'a, T: Ord, A: Allocator> Iterator for Union<'a, T, A> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next(UNION)
+    }
+}
+
+/// Iterator over the intersection of two sorted lists.
+pub struct Intersection<'a, T, A: Allocator = Global>(SetOpIter<'a, T, A>);
+
+impl<'a, T: Ord, A: Allocator> Iterator for Intersection<'a, T, A> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next(INTERSECTION)
+    }
+}
+
+/// Iterator over the difference of two sorted lists.
+pub struct Difference<'a, T, A: Allocator = Global>(SetOpIter<'a, T, A>);
+
+impl<'a, T: Ord, A: Allocator> Iterator for Difference<'a, T, A> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next(DIFFERENCE)
+    }
+}
+
+/// Iterator over the symmetric difference of two sorted lists.
+pub struct SymmetricDifference<'a, T, A: Allocator = Global>(SetOpIter<'a, T, A>);
+
+impl<'a, T: Ord, A: Allocator> Iterator for SymmetricDifference<'a, T, A> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next(SYMMETRIC_DIFFERENCE)
+    }
+}
+
+#[cfg(test)]
+fn mklist(s: &[u64]) -> List<u64> {
+    s.iter().cloned().collect()
+}
+
+#[test]
+fn set_ops() {
+    let a = mklist(&[1, 1, 2, 4, 6, 9]);
+    let b = mklist(&[1, 2, 2, 3, 6, 10, 11]);
+    let union = mklist(&[1, 1, 2, 2, 3, 4, 6, 9, 10, 11]);
+    let intersection = mklist(&[1, 2, 6]);
+    let difference = mklist(&[1, 4, 9]);
+    let symmetric_difference = mklist(&[1, 2, 3, 4, 9, 10, 11]);
+
+    assert!(a.union(&b).eq(union.iter()));
+    assert!(a.intersection(&b).eq(intersection.iter()));
+    assert!(a.difference(&b).eq(difference.iter()));
+    assert!(a.symmetric_difference(&b).eq(symmetric_difference.iter()));
+
+    let r = a.clone().into_union(b.clone());
+    r.check_links();
+    assert_eq!(r, union);
+    let r = a.clone().into_intersection(b.clone());
+    r.check_links();
+    assert_eq!(r, intersection);
+    let r = a.clone().into_difference(b.clone());
+    r.check_links();
+    assert_eq!(r, difference);
+    let r = a.clone().into_symmetric_difference(b);
+    r.check_links();
+    assert_eq!(r, symmetric_difference);
+
+    let e = List::new();
+    assert_eq!(a.clone().into_union(e.clone()), a);
+    assert_eq!(e.clone().into_difference(a.clone()), e);
+    assert_eq!(a.intersection(&e).count(), 0);
+    assert_eq!(e.symmetric_difference(&a).count(), a.len());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "list is not sorted")]
+fn set_ops_unsorted() {
+    mklist(&[1, 3, 2]).into_union(mklist(&[1, 2, 3]));
+}